@@ -1,5 +1,8 @@
+use blake2::Blake2b512;
 use criterion::{criterion_group, criterion_main, Criterion};
-use kem_combiners::{base::*, combiners::*, hybrid::*};
+use kem_combiners::{base::*, cfrg::*, combiners::*, hybrid::*};
+use sha2::{Sha256, Sha384, Sha512};
+use sha3::{digest::core_api::BlockSizeUser, Digest, Sha3_256, Sha3_512};
 
 fn bench_combiner<T, PQ, C>(
     c: &mut Criterion,
@@ -14,7 +17,7 @@ fn bench_combiner<T, PQ, C>(
     C: Combiner,
 {
     let mut rng = rand::thread_rng();
-    let (ct, ss) = HybridKem::encap(combo, &mut rng, &ek);
+    let (ct, ss) = HybridKem::encap(combo, &mut rng, ek);
 
     let ss_t = ss.as_ref();
     let ct_t = ct.t.as_ref();
@@ -33,18 +36,83 @@ fn bench_combiner<T, PQ, C>(
     let encap_label = format!("{}_{}_encap", kem_label, label);
     c.bench_function(&encap_label, |b| {
         b.iter(|| {
-            HybridKem::encap(combo, &mut rng, &ek);
+            HybridKem::encap(combo, &mut rng, ek);
         })
     });
 
     let decap_label = format!("{}_{}_decap", kem_label, label);
     c.bench_function(&decap_label, |b| {
         b.iter(|| {
-            HybridKem::decap(combo, &dk, &ct);
+            HybridKem::decap(combo, dk, &ct);
         })
     });
 }
 
+// The combiners from the README tables and the dual PRF, which are generic over the hash
+// function.  The suffix names the hash, and is empty for SHA3-256.
+fn bench_hash<T, PQ, H>(
+    c: &mut Criterion,
+    dk: &DecapsulationKey<T, PQ>,
+    ek: &EncapsulationKey<T, PQ>,
+    kem: &str,
+    suffix: &str,
+) where
+    T: BaseKem,
+    PQ: BaseKem,
+    H: Digest + BlockSizeUser + Clone,
+{
+    let label = |name: &str| format!("{}{}", name, suffix);
+
+    // Stateless
+    let kitchen_sink = KitchenSink::<H>::default();
+    bench_combiner(c, &kitchen_sink, dk, ek, kem, &label("kitchen_sink"));
+    let chempat = Chempat::<H>::default();
+    bench_combiner(c, &chempat, dk, ek, kem, &label("chempat"));
+    let dhkem = Dhkem::<H>::default();
+    bench_combiner(c, &dhkem, dk, ek, kem, &label("dhkem"));
+    let dhkem_half = DhkemHalf::<H>::default();
+    bench_combiner(c, &dhkem_half, dk, ek, kem, &label("dhkem_half"));
+    let xwing = XWing::<H>::default();
+    bench_combiner(c, &xwing, dk, ek, kem, &label("xwing"));
+    let dual_prf = DualPrf::<H>::default();
+    bench_combiner(c, &dual_prf, dk, ek, kem, &label("dual_prf"));
+
+    // Stateful
+    let kitchen_sink_pre = KitchenSinkPre::<H>::new_hybrid(ek);
+    bench_combiner(
+        c,
+        &kitchen_sink_pre,
+        dk,
+        ek,
+        kem,
+        &label("kitchen_sink_pre"),
+    );
+    let chempat_pre = ChempatPre::<H>::new_hybrid(ek);
+    bench_combiner(c, &chempat_pre, dk, ek, kem, &label("chempat_pre"));
+    let dhkem_pre = DhkemPre::<H>::new_hybrid(ek);
+    bench_combiner(c, &dhkem_pre, dk, ek, kem, &label("dhkem_pre"));
+    let dual_prf_pre = DualPrfPre::<H>::new_hybrid(ek);
+    bench_combiner(c, &dual_prf_pre, dk, ek, kem, &label("dual_prf_pre"));
+}
+
+// The same combiners with hash functions other than SHA3-256, to see whether the differences
+// between them hold up when Keccak is not the bottleneck
+pub fn bench_hashes<T, PQ>(c: &mut Criterion, kem: &str)
+where
+    T: BaseKem,
+    PQ: BaseKem,
+{
+    let mut rng = rand::thread_rng();
+    let (dk, ek) = HybridKem::<T, PQ>::generate(&mut rng);
+
+    bench_hash::<T, PQ, Sha256>(c, &dk, &ek, kem, "_sha256");
+    bench_hash::<T, PQ, Sha384>(c, &dk, &ek, kem, "_sha384");
+    bench_hash::<T, PQ, Sha512>(c, &dk, &ek, kem, "_sha512");
+    bench_hash::<T, PQ, Sha3_512>(c, &dk, &ek, kem, "_sha3_512");
+    bench_hash::<T, PQ, Blake2b512>(c, &dk, &ek, kem, "_blake2b");
+    bench_hash::<T, PQ, blake3::Hasher>(c, &dk, &ek, kem, "_blake3");
+}
+
 pub fn bench_hybrid<T, PQ>(c: &mut Criterion, kem: &str)
 where
     T: BaseKem,
@@ -53,27 +121,147 @@ where
     let mut rng = rand::thread_rng();
     let (dk, ek) = HybridKem::<T, PQ>::generate(&mut rng);
 
+    bench_hash::<T, PQ, Sha3_256>(c, &dk, &ek, kem, "");
+
     // Stateless
-    bench_combiner(c, &KitchenSink, &dk, &ek, kem, "kitchen_sink");
-    bench_combiner(c, &Chempat, &dk, &ek, kem, "chempat");
-    bench_combiner(c, &Dhkem, &dk, &ek, kem, "dhkem");
-    bench_combiner(c, &DhkemHalf, &dk, &ek, kem, "dhkem_half");
-    bench_combiner(c, &XWing, &dk, &ek, kem, "xwing");
+    bench_combiner(c, &XWingLabeled::new(), &dk, &ek, kem, "xwing_labeled");
+    bench_combiner(c, &Ghp::<Sha3_256>::new(b"GHP"), &dk, &ek, kem, "ghp");
+    bench_combiner(c, &Pre::<Sha3_256>::new(b"PRE"), &dk, &ek, kem, "pre");
+    bench_combiner(c, &Qsf::<Sha3_256>::new(b"QSF"), &dk, &ek, kem, "qsf");
+    let composite_sha3 = Composite::from(ID_MLKEM768_X25519);
+    bench_combiner(c, &composite_sha3, &dk, &ek, kem, "composite_sha3");
+    let composite_hmac = Composite::from(ID_MLKEM768_RSA2048);
+    bench_combiner(c, &composite_hmac, &dk, &ek, kem, "composite_hmac");
+    bench_combiner(c, &Tls13Sha256::new(), &dk, &ek, kem, "tls13_sha256");
+    bench_combiner(c, &Tls13Sha384::new(), &dk, &ek, kem, "tls13_sha384");
+
+    // IKEv2 with 32-byte nonces, the most common choice
+    let (ni, nr) = ([0x01; 32], [0x02; 32]);
+    let (spi_i, spi_r) = ([0x03; 8], [0x04; 8]);
+    let ikev2_sha256 = Ikev2Sha256::new(&ni, &nr, spi_i, spi_r);
+    bench_combiner(c, &ikev2_sha256, &dk, &ek, kem, "ikev2_sha256");
+    let ikev2_sha384 = Ikev2Sha384::new(&ni, &nr, spi_i, spi_r);
+    bench_combiner(c, &ikev2_sha384, &dk, &ek, kem, "ikev2_sha384");
+    let ikev2_sha512 = Ikev2Sha512::new(&ni, &nr, spi_i, spi_r);
+    bench_combiner(c, &ikev2_sha512, &dk, &ek, kem, "ikev2_sha512");
+
+    let alg_id = OPENPGP_MLKEM768_X25519;
+    bench_combiner(c, &OpenPgpKmac { alg_id }, &dk, &ek, kem, "openpgp_kmac");
+    bench_combiner(c, &OpenPgpSha3 { alg_id }, &dk, &ek, kem, "openpgp_sha3");
+
+    let cat_kdf = CatKdf::<HkdfSha256Kdf>::new(&[], b"ETSI", &[]);
+    bench_combiner(c, &cat_kdf, &dk, &ek, kem, "cat_kdf_hkdf");
+    let cas_kdf = CasKdf::<HkdfSha256Kdf>::new(&[], b"ETSI", &[]);
+    bench_combiner(c, &cas_kdf, &dk, &ek, kem, "cas_kdf_hkdf");
+    let cat_kdf = CatKdf::<Kmac256Kdf>::new(&[], b"ETSI", &[]);
+    bench_combiner(c, &cat_kdf, &dk, &ek, kem, "cat_kdf_kmac");
+    let cas_kdf = CasKdf::<Kmac256Kdf>::new(&[], b"ETSI", &[]);
+    bench_combiner(c, &cas_kdf, &dk, &ek, kem, "cas_kdf_kmac");
+
+    let pqxdh = Pqxdh {
+        info: PQXDH_SIGNAL_INFO.to_vec(),
+    };
+    bench_combiner(c, &pqxdh, &dk, &ek, kem, "pqxdh");
+    bench_combiner(c, &SshSha256::new(), &dk, &ek, kem, "ssh_sha256");
+    bench_combiner(c, &SshSha512::new(), &dk, &ek, kem, "ssh_sha512");
 
     // Stateful
-    let kitchen_sink_pre = KitchenSinkPre::new_hybrid(&ek);
-    bench_combiner(c, &kitchen_sink_pre, &dk, &ek, kem, "kitchen_sink_pre");
+    let pre_pre = PrePre::<Sha3_256>::new(b"PRE", ek.t.as_ref(), ek.pq.as_ref());
+    bench_combiner(c, &pre_pre, &dk, &ek, kem, "pre_pre");
+}
 
-    let chempat_pre = ChempatPre::new_hybrid(&ek);
-    bench_combiner(c, &chempat_pre, &dk, &ek, kem, "chempat_pre");
+// A labeled Chempat instance, on the component pair that it names
+pub fn bench_chempat<T, PQ, H>(c: &mut Criterion, chempat: &ChempatLabeled<H>, kem: &str)
+where
+    T: BaseKem,
+    PQ: BaseKem,
+    H: Digest,
+{
+    let mut rng = rand::thread_rng();
+    let (dk, ek) = HybridKem::<T, PQ>::generate(&mut rng);
+    bench_combiner(c, chempat, &dk, &ek, kem, "chempat_labeled");
+}
+
+// Named hybrid KEMs that fix their own combiner and encodings
+pub fn bench_kem<K: BaseKem>(c: &mut Criterion, kem: &str) {
+    let mut rng = rand::thread_rng();
+    let (dk, ek) = K::generate(&mut rng);
+    let (ct, _) = K::encap(&mut rng, &ek);
 
-    let dhkem_pre = DhkemPre::new_hybrid(&ek);
-    bench_combiner(c, &dhkem_pre, &dk, &ek, kem, "dhkem_pre");
+    c.bench_function(&format!("{}_encap", kem), |b| {
+        b.iter(|| {
+            K::encap(&mut rng, &ek);
+        })
+    });
+
+    c.bench_function(&format!("{}_decap", kem), |b| {
+        b.iter(|| {
+            K::decap(&dk, &ct);
+        })
+    });
+}
+
+// Key generation, random and from a seed.  Classic McEliece keygen takes long enough that the
+// sample count is kept at criterion's minimum.
+pub fn bench_keygen<K: SeedKem>(c: &mut Criterion, kem: &str) {
+    let mut rng = rand::thread_rng();
+    let seed = vec![0x42; K::SEED_LEN];
+
+    let mut group = c.benchmark_group(format!("{}_keygen", kem));
+    group.sample_size(10);
+    group.bench_function("random", |b| {
+        b.iter(|| {
+            K::generate(&mut rng);
+        })
+    });
+    group.bench_function("seed", |b| {
+        b.iter(|| {
+            K::generate_from_seed(&seed);
+        })
+    });
+    group.finish();
 }
 
 pub fn criterion_benchmark(c: &mut Criterion) {
-    bench_hybrid::<X25519, MlKem>(c, "x25510_ml_kem");
-    bench_hybrid::<X25519, ClassicMcEliece>(c, "x25510_classic_mceliece");
+    bench_hybrid::<X25519, MlKem512>(c, "x25519_ml_kem_512");
+    bench_hybrid::<X25519, MlKem768>(c, "x25519_ml_kem_768");
+    bench_hybrid::<X25519, MlKem1024>(c, "x25519_ml_kem_1024");
+    bench_hybrid::<RsaKem2048, MlKem768>(c, "rsa_kem_2048_ml_kem_768");
+    bench_hybrid::<RsaKem3072, MlKem768>(c, "rsa_kem_3072_ml_kem_768");
+    bench_hybrid::<RsaKem4096, MlKem1024>(c, "rsa_kem_4096_ml_kem_1024");
+    bench_hybrid::<X25519, Kyber768Round3>(c, "x25519_kyber768_round3");
+    bench_hybrid::<X448, MlKem1024>(c, "x448_ml_kem_1024");
+    bench_hybrid::<DhkemX25519HkdfSha256, MlKem768>(c, "dhkem_x25519_ml_kem_768");
+    bench_hybrid::<DhkemP256HkdfSha256, MlKem768>(c, "dhkem_p256_ml_kem_768");
+    bench_hybrid::<P256, MlKem768>(c, "p256_ml_kem_768");
+    bench_hybrid::<P384, MlKem1024>(c, "p384_ml_kem_1024");
+    bench_hybrid::<P521, MlKem1024>(c, "p521_ml_kem_1024");
+    bench_hybrid::<X25519, FrodoKem640Aes>(c, "x25519_frodo_kem_640_aes");
+    bench_hybrid::<X25519, FrodoKem640Shake>(c, "x25519_frodo_kem_640_shake");
+    bench_hybrid::<X25519, FrodoKem976Shake>(c, "x25519_frodo_kem_976_shake");
+    bench_hybrid::<X25519, FrodoKem1344Shake>(c, "x25519_frodo_kem_1344_shake");
+    bench_hybrid::<X25519, Sntrup761>(c, "x25519_sntrup761");
+    bench_hybrid::<X25519, Hqc128>(c, "x25519_hqc_128");
+    bench_hybrid::<P384, Hqc192>(c, "p384_hqc_192");
+    bench_hybrid::<X448, Hqc256>(c, "x448_hqc_256");
+
+    let kem = format!("x25519_classic_{}", ClassicMcEliece::PARAMETER_SET);
+    bench_hybrid::<X25519, ClassicMcEliece>(c, &kem);
+
+    // The keygen difference between the f and non-f parameter sets
+    bench_keygen::<ClassicMcEliece>(c, &format!("classic_{}", ClassicMcEliece::PARAMETER_SET));
+
+    bench_hashes::<X25519, MlKem768>(c, "x25519_ml_kem_768");
+    bench_hashes::<X25519, ClassicMcEliece>(c, &kem);
+
+    bench_chempat::<X25519, Sntrup761, _>(c, &CHEMPAT_X25519_SNTRUP761, "x25519_sntrup761");
+    bench_chempat::<X25519, MlKem768, _>(c, &CHEMPAT_X25519_MLKEM768, "x25519_ml_kem_768");
+    bench_chempat::<X448, MlKem1024, _>(c, &CHEMPAT_X448_MLKEM1024, "x448_ml_kem_1024");
+    bench_chempat::<X448, Sntrup761, _>(c, &CHEMPAT_X448_SNTRUP761, "x448_sntrup761");
+
+    bench_kem::<QsfX25519MlKem768>(c, "qsf_x25519_ml_kem_768");
+    bench_kem::<QsfP256MlKem768>(c, "qsf_p256_ml_kem_768");
+    bench_kem::<QsfP384MlKem1024>(c, "qsf_p384_ml_kem_1024");
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -1,12 +1,26 @@
+use std::marker::PhantomData;
+
+use elliptic_curve::{
+    ecdh::{diffie_hellman, EphemeralSecret},
+    generic_array::typenum::Unsigned,
+    sec1::{EncodedPoint, FromEncodedPoint, ModulusSize, ToEncodedPoint},
+    AffinePoint, CurveArithmetic, FieldBytes, FieldBytesSize, NonZeroScalar, PublicKey,
+};
+use hkdf::{Hkdf, HkdfExtract};
 use hybrid_array::{
-    sizes::{U1088, U32},
+    sizes::{U32, U56},
     Array,
 };
 use ml_kem::{
     kem::{Decapsulate, Encapsulate},
-    Encoded, EncodedSizeUser, KemCore, MlKem768,
+    EncapsulateDeterministic, Encoded, EncodedSizeUser, KemCore, B32,
 };
-use rand_core::CryptoRngCore;
+use rand_chacha::ChaCha20Rng;
+use rand_core::{CryptoRng, CryptoRngCore, RngCore, SeedableRng};
+use rsa::{
+    pkcs1::EncodeRsaPublicKey, traits::PublicKeyParts, BigUint, RsaPrivateKey, RsaPublicKey,
+};
+use sha2::{digest::Output, Digest, Sha256};
 
 pub trait BaseKem {
     type DecapsulationKey;
@@ -24,6 +38,41 @@ pub trait BaseKem {
     fn decap(dk: &Self::DecapsulationKey, ct: &Self::Ciphertext) -> Self::SharedSecret;
 }
 
+// A KEM whose key pairs can be generated deterministically from a seed.  The ID names the
+// algorithm, so that a hybrid can bind its components when deriving their seeds.
+pub trait SeedKem: BaseKem {
+    const ID: &'static [u8];
+    const SEED_LEN: usize;
+
+    fn generate_from_seed(seed: &[u8]) -> (Self::DecapsulationKey, Self::EncapsulationKey);
+}
+
+// A KEM whose encapsulation can be run on caller-supplied randomness, e.g., to produce
+// known-answer tests
+pub trait DerandKem: BaseKem {
+    const ENCAP_RANDOMNESS_LEN: usize;
+
+    fn encap_derand(
+        ek: &Self::EncapsulationKey,
+        randomness: &[u8],
+    ) -> (Self::Ciphertext, Self::SharedSecret);
+}
+
+// The error returned when an encoded key or ciphertext is malformed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DecodeError;
+
+// A KEM whose encapsulation keys and ciphertexts can be parsed from their encodings, so that we
+// can encapsulate to a peer's key or decapsulate a ciphertext received from one.  The lengths
+// are fixed, so that hybrids can split concatenated encodings.
+pub trait DecodeKem: BaseKem {
+    const EK_LEN: usize;
+    const CT_LEN: usize;
+
+    fn decode_ek(bytes: &[u8]) -> Result<Self::EncapsulationKey, DecodeError>;
+    fn decode_ct(bytes: &[u8]) -> Result<Self::Ciphertext, DecodeError>;
+}
+
 // Raw X25519
 pub struct X25519;
 
@@ -45,47 +94,486 @@ impl BaseKem for X25519 {
     ) -> (Self::Ciphertext, Self::SharedSecret) {
         let sk_e = x25519_dalek::EphemeralSecret::random_from_rng(rng);
         let ct = x25519_dalek::PublicKey::from(&sk_e);
-        let ss = sk_e.diffie_hellman(&ek);
+        let ss = sk_e.diffie_hellman(ek);
+        (ct, ss)
+    }
+
+    fn decap(dk: &Self::DecapsulationKey, ct: &Self::Ciphertext) -> Self::SharedSecret {
+        dk.diffie_hellman(ct)
+    }
+}
+
+// The seed is the secret scalar
+impl SeedKem for X25519 {
+    const ID: &'static [u8] = b"X25519";
+    const SEED_LEN: usize = 32;
+
+    fn generate_from_seed(seed: &[u8]) -> (Self::DecapsulationKey, Self::EncapsulationKey) {
+        assert_eq!(seed.len(), Self::SEED_LEN);
+        let seed: [u8; 32] = seed.try_into().unwrap();
+        let dk = x25519_dalek::StaticSecret::from(seed);
+        let ek = x25519_dalek::PublicKey::from(&dk);
+        (dk, ek)
+    }
+}
+
+// The randomness is the ephemeral secret scalar
+impl DerandKem for X25519 {
+    const ENCAP_RANDOMNESS_LEN: usize = 32;
+
+    fn encap_derand(
+        ek: &Self::EncapsulationKey,
+        randomness: &[u8],
+    ) -> (Self::Ciphertext, Self::SharedSecret) {
+        let sk_e: [u8; 32] = randomness.try_into().unwrap();
+        let sk_e = x25519_dalek::StaticSecret::from(sk_e);
+        let ct = x25519_dalek::PublicKey::from(&sk_e);
+        let ss = sk_e.diffie_hellman(ek);
+        (ct, ss)
+    }
+}
+
+// Every 32-byte string is a valid Montgomery u-coordinate
+impl DecodeKem for X25519 {
+    const EK_LEN: usize = 32;
+    const CT_LEN: usize = 32;
+
+    fn decode_ek(bytes: &[u8]) -> Result<Self::EncapsulationKey, DecodeError> {
+        let bytes: [u8; 32] = bytes.try_into().map_err(|_| DecodeError)?;
+        Ok(x25519_dalek::PublicKey::from(bytes))
+    }
+
+    fn decode_ct(bytes: &[u8]) -> Result<Self::Ciphertext, DecodeError> {
+        Self::decode_ek(bytes)
+    }
+}
+
+// Raw X448
+pub struct X448;
+
+impl BaseKem for X448 {
+    type DecapsulationKey = Array<u8, U56>;
+    type EncapsulationKey = Array<u8, U56>;
+    type Ciphertext = Array<u8, U56>;
+    type SharedSecret = Array<u8, U56>;
+
+    fn generate(rng: &mut impl CryptoRngCore) -> (Self::DecapsulationKey, Self::EncapsulationKey) {
+        let mut dk = Array::<u8, U56>::default();
+        rng.fill_bytes(&mut dk);
+        let ek = x448::x448_unchecked(dk.into(), x448::X448_BASEPOINT_BYTES);
+        (dk, ek.into())
+    }
+
+    fn encap(
+        rng: &mut impl CryptoRngCore,
+        ek: &Self::EncapsulationKey,
+    ) -> (Self::Ciphertext, Self::SharedSecret) {
+        let mut sk_e = [0u8; 56];
+        rng.fill_bytes(&mut sk_e);
+        let ct = x448::x448_unchecked(sk_e, x448::X448_BASEPOINT_BYTES);
+        let ss = x448::x448_unchecked(sk_e, (*ek).into());
+        (ct.into(), ss.into())
+    }
+
+    fn decap(dk: &Self::DecapsulationKey, ct: &Self::Ciphertext) -> Self::SharedSecret {
+        x448::x448_unchecked((*dk).into(), (*ct).into()).into()
+    }
+}
+
+// Ephemeral-static ECDH over a NIST curve, with keys and ciphertexts encoded as uncompressed
+// SEC1 points
+pub struct Ecdh<C>(PhantomData<C>);
+
+pub type P256 = Ecdh<p256::NistP256>;
+pub type P384 = Ecdh<p384::NistP384>;
+pub type P521 = Ecdh<p521::NistP521>;
+
+impl<C> BaseKem for Ecdh<C>
+where
+    C: CurveArithmetic,
+    FieldBytesSize<C>: ModulusSize,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+{
+    type DecapsulationKey = NonZeroScalar<C>;
+    type EncapsulationKey = EncodedPoint<C>;
+    type Ciphertext = EncodedPoint<C>;
+    type SharedSecret = FieldBytes<C>;
+
+    fn generate(rng: &mut impl CryptoRngCore) -> (Self::DecapsulationKey, Self::EncapsulationKey) {
+        let dk = NonZeroScalar::random(rng);
+        let ek = PublicKey::<C>::from_secret_scalar(&dk).to_encoded_point(false);
+        (dk, ek)
+    }
+
+    fn encap(
+        rng: &mut impl CryptoRngCore,
+        ek: &Self::EncapsulationKey,
+    ) -> (Self::Ciphertext, Self::SharedSecret) {
+        let pk: PublicKey<C> =
+            Option::from(PublicKey::from_encoded_point(ek)).expect("invalid encapsulation key");
+        let sk_e = EphemeralSecret::<C>::random(rng);
+        let ct = sk_e.public_key().to_encoded_point(false);
+        let ss = sk_e.diffie_hellman(&pk).raw_secret_bytes().clone();
         (ct, ss)
     }
 
     fn decap(dk: &Self::DecapsulationKey, ct: &Self::Ciphertext) -> Self::SharedSecret {
-        dk.diffie_hellman(&ct)
+        let pk: PublicKey<C> =
+            Option::from(PublicKey::from_encoded_point(ct)).expect("invalid ciphertext");
+        diffie_hellman(dk, pk.as_affine())
+            .raw_secret_bytes()
+            .clone()
+    }
+}
+
+impl<C> Ecdh<C>
+where
+    C: CurveArithmetic,
+    FieldBytesSize<C>: ModulusSize,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+{
+    // RandomScalar from draft-irtf-cfrg-concrete-hybrid-kems: The seed is split into
+    // scalar-sized chunks, and the first one that encodes a valid nonzero scalar is used
+    fn random_scalar(seed: &[u8]) -> NonZeroScalar<C> {
+        seed.chunks_exact(C::FieldBytesSize::USIZE)
+            .find_map(|chunk| {
+                Option::from(NonZeroScalar::from_repr(FieldBytes::<C>::clone_from_slice(
+                    chunk,
+                )))
+            })
+            .expect("rejection sampling failed")
+    }
+
+    fn generate_from_scalar(dk: NonZeroScalar<C>) -> (NonZeroScalar<C>, EncodedPoint<C>) {
+        let ek = PublicKey::<C>::from_secret_scalar(&dk).to_encoded_point(false);
+        (dk, ek)
+    }
+
+    fn encap_with_scalar(
+        sk_e: NonZeroScalar<C>,
+        ek: &EncodedPoint<C>,
+    ) -> (EncodedPoint<C>, FieldBytes<C>) {
+        let (sk_e, ct) = Self::generate_from_scalar(sk_e);
+        let pk: PublicKey<C> =
+            Option::from(PublicKey::from_encoded_point(ek)).expect("invalid encapsulation key");
+        let ss = diffie_hellman(sk_e, pk.as_affine())
+            .raw_secret_bytes()
+            .clone();
+        (ct, ss)
     }
 }
 
-// ML-KEM-768
-pub type MlKemDecapsulationKey = <MlKem768 as KemCore>::DecapsulationKey;
+// Only uncompressed points are accepted, and they must lie on the curve
+impl<C> DecodeKem for Ecdh<C>
+where
+    C: CurveArithmetic,
+    FieldBytesSize<C>: ModulusSize,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+{
+    const EK_LEN: usize = 1 + 2 * C::FieldBytesSize::USIZE;
+    const CT_LEN: usize = Self::EK_LEN;
+
+    fn decode_ek(bytes: &[u8]) -> Result<Self::EncapsulationKey, DecodeError> {
+        if bytes.len() != Self::EK_LEN {
+            return Err(DecodeError);
+        }
+        let point = EncodedPoint::<C>::from_bytes(bytes).map_err(|_| DecodeError)?;
+        Option::<PublicKey<C>>::from(PublicKey::from_encoded_point(&point)).ok_or(DecodeError)?;
+        Ok(point)
+    }
+
+    fn decode_ct(bytes: &[u8]) -> Result<Self::Ciphertext, DecodeError> {
+        Self::decode_ek(bytes)
+    }
+}
+
+// Seeds and encapsulation randomness are sampled into scalars with RandomScalar, using the
+// seed lengths from draft-irtf-cfrg-concrete-hybrid-kems
+macro_rules! ecdh_seed_len {
+    ($($ecdh:ident => $id:literal, $seed_len:expr,)*) => {
+        $(
+            impl SeedKem for $ecdh {
+                const ID: &'static [u8] = $id;
+                const SEED_LEN: usize = $seed_len;
+
+                fn generate_from_seed(seed: &[u8]) -> (Self::DecapsulationKey, Self::EncapsulationKey) {
+                    assert_eq!(seed.len(), Self::SEED_LEN);
+                    Self::generate_from_scalar(Self::random_scalar(seed))
+                }
+            }
+
+            impl DerandKem for $ecdh {
+                const ENCAP_RANDOMNESS_LEN: usize = $seed_len;
+
+                fn encap_derand(
+                    ek: &Self::EncapsulationKey,
+                    randomness: &[u8],
+                ) -> (Self::Ciphertext, Self::SharedSecret) {
+                    assert_eq!(randomness.len(), Self::ENCAP_RANDOMNESS_LEN);
+                    Self::encap_with_scalar(Self::random_scalar(randomness), ek)
+                }
+            }
+        )*
+    };
+}
+
+ecdh_seed_len! {
+    P256 => b"P-256", 128,
+    P384 => b"P-384", 48,
+}
 
+// RSA-KEM, using RSASVE from SP 800-56B to transport a random integer and KDF3 with SHA-256
+// from RFC 5990 to derive the shared secret from it.  The encapsulation key is represented by
+// its PKCS#1 DER encoding.  Decapsulation blinds the RSA private-key operation with randomness
+// from `rand::thread_rng()`, since `BaseKem::decap` does not take an RNG.
 #[derive(Clone)]
-pub struct MlKemEncapsulationKey {
-    ek: <MlKem768 as KemCore>::EncapsulationKey,
-    ek_bytes: Encoded<<MlKem768 as KemCore>::EncapsulationKey>,
+pub struct RsaKemEncapsulationKey {
+    ek: RsaPublicKey,
+    ek_bytes: Vec<u8>,
+}
+
+impl RsaKemEncapsulationKey {
+    fn new(ek: RsaPublicKey) -> Self {
+        let ek_bytes = ek.to_pkcs1_der().unwrap().into_vec();
+        Self { ek, ek_bytes }
+    }
+}
+
+impl AsRef<[u8]> for RsaKemEncapsulationKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.ek_bytes
+    }
+}
+
+pub struct RsaKem<const BITS: usize>;
+
+pub type RsaKem2048 = RsaKem<2048>;
+pub type RsaKem3072 = RsaKem<3072>;
+pub type RsaKem4096 = RsaKem<4096>;
+
+impl<const BITS: usize> RsaKem<BITS> {
+    fn i2osp(x: &BigUint, n_len: usize) -> Vec<u8> {
+        let bytes = x.to_bytes_be();
+        let mut out = vec![0u8; n_len - bytes.len()];
+        out.extend_from_slice(&bytes);
+        out
+    }
+
+    // KDF3 from ANSI X9.44: Hash(counter || Z || OtherInfo), with a 32-bit big-endian counter
+    // starting at 1.  This is the same construction as the one-step KDF from SP 800-56A.
+    fn kdf3(z: &[u8], other_info: &[u8], out: &mut [u8]) {
+        for (i, chunk) in out.chunks_mut(Sha256::output_size()).enumerate() {
+            let mut h = Sha256::new();
+            h.update((i as u32 + 1).to_be_bytes());
+            h.update(z);
+            h.update(other_info);
+            chunk.copy_from_slice(&h.finalize()[..chunk.len()]);
+        }
+    }
+
+    fn shared_secret(z: &BigUint, n_len: usize) -> Output<Sha256> {
+        let mut ss = Output::<Sha256>::default();
+        Self::kdf3(&Self::i2osp(z, n_len), &[], &mut ss);
+        ss
+    }
+}
+
+impl<const BITS: usize> BaseKem for RsaKem<BITS> {
+    type DecapsulationKey = RsaPrivateKey;
+    type EncapsulationKey = RsaKemEncapsulationKey;
+    type Ciphertext = Vec<u8>;
+    type SharedSecret = Output<Sha256>;
+
+    fn generate(rng: &mut impl CryptoRngCore) -> (Self::DecapsulationKey, Self::EncapsulationKey) {
+        let dk = RsaPrivateKey::new(rng, BITS).unwrap();
+        let ek = RsaKemEncapsulationKey::new(dk.to_public_key());
+        (dk, ek)
+    }
+
+    fn encap(
+        rng: &mut impl CryptoRngCore,
+        ek: &Self::EncapsulationKey,
+    ) -> (Self::Ciphertext, Self::SharedSecret) {
+        let n = ek.ek.n();
+        let n_len = ek.ek.size();
+
+        // Sample z uniformly from [2, n - 2] by rejection
+        let two = BigUint::from(2u8);
+        let mut z_bytes = vec![0u8; n_len];
+        let z = loop {
+            rng.fill_bytes(&mut z_bytes);
+            let z = BigUint::from_bytes_be(&z_bytes);
+            if z >= two && z <= n - &two {
+                break z;
+            }
+        };
+
+        let c = rsa::hazmat::rsa_encrypt(&ek.ek, &z).unwrap();
+        let ct = Self::i2osp(&c, n_len);
+        let ss = Self::shared_secret(&z, n_len);
+        (ct, ss)
+    }
+
+    fn decap(dk: &Self::DecapsulationKey, ct: &Self::Ciphertext) -> Self::SharedSecret {
+        let n_len = dk.size();
+        assert_eq!(ct.len(), n_len, "invalid ciphertext");
+
+        let c = BigUint::from_bytes_be(ct);
+        let mut rng = rand::thread_rng();
+        let z =
+            rsa::hazmat::rsa_decrypt_and_check(dk, Some(&mut rng), &c).expect("invalid ciphertext");
+        Self::shared_secret(&z, n_len)
+    }
+}
+
+// DHKEM from RFC 9180, with HKDF-SHA256 run over one of the raw DH KEMs above
+pub trait DhkemGroup: BaseKem {
+    const KEM_ID: u16;
+}
+
+impl DhkemGroup for X25519 {
+    const KEM_ID: u16 = 0x0020;
+}
+
+impl DhkemGroup for P256 {
+    const KEM_ID: u16 = 0x0010;
+}
+
+pub struct HpkeDhkemDecapsulationKey<G: DhkemGroup> {
+    dk: G::DecapsulationKey,
+    ek: G::EncapsulationKey,
+}
+
+pub struct HpkeDhkem<G>(PhantomData<G>);
+
+pub type DhkemX25519HkdfSha256 = HpkeDhkem<X25519>;
+pub type DhkemP256HkdfSha256 = HpkeDhkem<P256>;
+
+impl<G: DhkemGroup> HpkeDhkem<G> {
+    fn suite_id() -> [u8; 5] {
+        let [hi, lo] = G::KEM_ID.to_be_bytes();
+        [b'K', b'E', b'M', hi, lo]
+    }
+
+    fn extract_and_expand(dh: &[u8], enc: &[u8], pk_r: &[u8]) -> Array<u8, U32> {
+        let suite_id = Self::suite_id();
+
+        // eae_prk = LabeledExtract("", "eae_prk", dh)
+        let mut extract = HkdfExtract::<Sha256>::new(None);
+        extract.input_ikm(b"HPKE-v1");
+        extract.input_ikm(&suite_id);
+        extract.input_ikm(b"eae_prk");
+        extract.input_ikm(dh);
+        let (_, eae_prk) = extract.finalize();
+
+        // shared_secret = LabeledExpand(eae_prk, "shared_secret", kem_context, Nsecret)
+        let mut ss = Array::<u8, U32>::default();
+        let length = (ss.len() as u16).to_be_bytes();
+        Hkdf::<Sha256>::expand_multi_info(
+            &eae_prk,
+            &[&length, b"HPKE-v1", &suite_id, b"shared_secret", enc, pk_r],
+            &mut ss,
+        )
+        .unwrap();
+        ss
+    }
+}
+
+impl<G: DhkemGroup> BaseKem for HpkeDhkem<G> {
+    type DecapsulationKey = HpkeDhkemDecapsulationKey<G>;
+    type EncapsulationKey = G::EncapsulationKey;
+    type Ciphertext = G::Ciphertext;
+    type SharedSecret = Array<u8, U32>;
+
+    fn generate(rng: &mut impl CryptoRngCore) -> (Self::DecapsulationKey, Self::EncapsulationKey) {
+        let (dk, ek) = G::generate(rng);
+        let dk = HpkeDhkemDecapsulationKey { dk, ek: ek.clone() };
+        (dk, ek)
+    }
+
+    fn encap(
+        rng: &mut impl CryptoRngCore,
+        ek: &Self::EncapsulationKey,
+    ) -> (Self::Ciphertext, Self::SharedSecret) {
+        let (enc, dh) = G::encap(rng, ek);
+        let ss = Self::extract_and_expand(dh.as_ref(), enc.as_ref(), ek.as_ref());
+        (enc, ss)
+    }
+
+    fn decap(dk: &Self::DecapsulationKey, ct: &Self::Ciphertext) -> Self::SharedSecret {
+        let dh = G::decap(&dk.dk, ct);
+        Self::extract_and_expand(dh.as_ref(), ct.as_ref(), dk.ek.as_ref())
+    }
+}
+
+// ML-KEM, generic over the FIPS 203 parameter set
+pub trait MlKemParams: KemCore {
+    const ID: &'static [u8];
+}
+
+impl MlKemParams for ml_kem::MlKem512 {
+    const ID: &'static [u8] = b"ML-KEM-512";
+}
+
+impl MlKemParams for ml_kem::MlKem768 {
+    const ID: &'static [u8] = b"ML-KEM-768";
+}
+
+impl MlKemParams for ml_kem::MlKem1024 {
+    const ID: &'static [u8] = b"ML-KEM-1024";
+}
+
+pub type MlKemDecapsulationKey<K = ml_kem::MlKem768> = <K as KemCore>::DecapsulationKey;
+
+pub struct MlKemEncapsulationKey<K: KemCore = ml_kem::MlKem768> {
+    ek: K::EncapsulationKey,
+    ek_bytes: Encoded<K::EncapsulationKey>,
 }
 
-impl MlKemEncapsulationKey {
-    fn new(ek: <MlKem768 as KemCore>::EncapsulationKey) -> Self {
+impl<K: KemCore> MlKemEncapsulationKey<K> {
+    fn new(ek: K::EncapsulationKey) -> Self {
         let ek_bytes = ek.as_bytes();
         Self { ek, ek_bytes }
     }
 }
 
-impl AsRef<[u8]> for MlKemEncapsulationKey {
+impl<K> Clone for MlKemEncapsulationKey<K>
+where
+    K: KemCore,
+    K::EncapsulationKey: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            ek: self.ek.clone(),
+            ek_bytes: self.ek_bytes.clone(),
+        }
+    }
+}
+
+impl<K: KemCore> AsRef<[u8]> for MlKemEncapsulationKey<K> {
     fn as_ref(&self) -> &[u8] {
         self.ek_bytes.as_slice()
     }
 }
 
-pub struct MlKem;
+pub struct MlKem<K = ml_kem::MlKem768>(PhantomData<K>);
 
-impl BaseKem for MlKem {
-    type DecapsulationKey = MlKemDecapsulationKey;
-    type EncapsulationKey = MlKemEncapsulationKey;
-    type Ciphertext = Array<u8, U1088>;
-    type SharedSecret = Array<u8, U32>;
+pub type MlKem512 = MlKem<ml_kem::MlKem512>;
+pub type MlKem768 = MlKem<ml_kem::MlKem768>;
+pub type MlKem1024 = MlKem<ml_kem::MlKem1024>;
+
+impl<K> BaseKem for MlKem<K>
+where
+    K: KemCore,
+    K::EncapsulationKey: Clone,
+{
+    type DecapsulationKey = MlKemDecapsulationKey<K>;
+    type EncapsulationKey = MlKemEncapsulationKey<K>;
+    type Ciphertext = ml_kem::Ciphertext<K>;
+    type SharedSecret = ml_kem::SharedKey<K>;
 
     fn generate(rng: &mut impl CryptoRngCore) -> (Self::DecapsulationKey, Self::EncapsulationKey) {
-        let (dk, ek) = MlKem768::generate(&mut *rng);
+        let (dk, ek) = K::generate(&mut *rng);
         (dk, MlKemEncapsulationKey::new(ek))
     }
 
@@ -97,11 +585,251 @@ impl BaseKem for MlKem {
     }
 
     fn decap(dk: &Self::DecapsulationKey, ct: &Self::Ciphertext) -> Self::SharedSecret {
-        dk.decapsulate(&ct).unwrap()
+        dk.decapsulate(ct).unwrap()
+    }
+}
+
+// The seed is the FIPS 203 seed (d, z)
+impl<K> SeedKem for MlKem<K>
+where
+    K: MlKemParams,
+    K::EncapsulationKey: Clone,
+{
+    const ID: &'static [u8] = K::ID;
+    const SEED_LEN: usize = 64;
+
+    fn generate_from_seed(seed: &[u8]) -> (Self::DecapsulationKey, Self::EncapsulationKey) {
+        assert_eq!(seed.len(), Self::SEED_LEN);
+        let (d, z) = seed.split_at(32);
+        let d = B32::try_from(d).unwrap();
+        let z = B32::try_from(z).unwrap();
+        let (dk, ek) = K::generate_deterministic(&d, &z);
+        (dk, MlKemEncapsulationKey::new(ek))
+    }
+}
+
+// The randomness is the message `m` input to ML-KEM.Encaps_internal
+impl<K> DerandKem for MlKem<K>
+where
+    K: KemCore,
+    K::EncapsulationKey: Clone,
+{
+    const ENCAP_RANDOMNESS_LEN: usize = 32;
+
+    fn encap_derand(
+        ek: &Self::EncapsulationKey,
+        randomness: &[u8],
+    ) -> (Self::Ciphertext, Self::SharedSecret) {
+        let m = B32::try_from(randomness).unwrap();
+        ek.ek.encapsulate_deterministic(&m).unwrap()
+    }
+}
+
+// Encapsulation keys must pass the modulus check from FIPS 203, section 7.2: Every 12-bit
+// coefficient of the encoded vector t, i.e., everything but the trailing 32-byte rho, must be
+// less than q.
+impl<K> DecodeKem for MlKem<K>
+where
+    K: KemCore,
+    K::EncapsulationKey: Clone,
+{
+    const EK_LEN: usize = <K::EncapsulationKey as EncodedSizeUser>::EncodedSize::USIZE;
+    const CT_LEN: usize = K::CiphertextSize::USIZE;
+
+    fn decode_ek(bytes: &[u8]) -> Result<Self::EncapsulationKey, DecodeError> {
+        const Q: u16 = 3329;
+
+        let ek_bytes = Encoded::<K::EncapsulationKey>::try_from(bytes).map_err(|_| DecodeError)?;
+        let (t, _rho) = bytes.split_at(bytes.len() - 32);
+        for c in t.chunks_exact(3) {
+            let c_0 = u16::from(c[0]) | (u16::from(c[1] & 0x0f) << 8);
+            let c_1 = u16::from(c[1] >> 4) | (u16::from(c[2]) << 4);
+            if c_0 >= Q || c_1 >= Q {
+                return Err(DecodeError);
+            }
+        }
+
+        let ek = K::EncapsulationKey::from_bytes(&ek_bytes);
+        Ok(MlKemEncapsulationKey { ek, ek_bytes })
+    }
+
+    fn decode_ct(bytes: &[u8]) -> Result<Self::Ciphertext, DecodeError> {
+        ml_kem::Ciphertext::<K>::try_from(bytes).map_err(|_| DecodeError)
+    }
+}
+
+// Round-3 Kyber768, as used by the X25519Kyber768Draft00 TLS codepoint.  This is not
+// compatible with ML-KEM-768.
+//
+// pqc_kyber 0.7.1 has the unpatched KyberSlash timing leak in decapsulation (RUSTSEC-2023-0079).
+// Only use this for benchmarks and on captured traffic, never with a key that decapsulates
+// ciphertexts an attacker can choose.
+pub struct Kyber768Round3;
+
+impl BaseKem for Kyber768Round3 {
+    type DecapsulationKey = pqc_kyber::SecretKey;
+    type EncapsulationKey = pqc_kyber::PublicKey;
+    type Ciphertext = [u8; pqc_kyber::KYBER_CIPHERTEXTBYTES];
+    type SharedSecret = pqc_kyber::SharedSecret;
+
+    fn generate(rng: &mut impl CryptoRngCore) -> (Self::DecapsulationKey, Self::EncapsulationKey) {
+        let keypair = pqc_kyber::keypair(rng).unwrap();
+        (keypair.secret, keypair.public)
+    }
+
+    fn encap(
+        rng: &mut impl CryptoRngCore,
+        ek: &Self::EncapsulationKey,
+    ) -> (Self::Ciphertext, Self::SharedSecret) {
+        pqc_kyber::encapsulate(ek, rng).unwrap()
+    }
+
+    fn decap(dk: &Self::DecapsulationKey, ct: &Self::Ciphertext) -> Self::SharedSecret {
+        pqc_kyber::decapsulate(ct, dk).unwrap()
+    }
+}
+
+// FrodoKEM
+pub trait FrodoParams {
+    const ALGORITHM: frodo_kem_rs::Algorithm;
+}
+
+macro_rules! frodo_params {
+    ($($params:ident => $alg:ident,)*) => {
+        $(
+            pub struct $params;
+
+            impl FrodoParams for $params {
+                const ALGORITHM: frodo_kem_rs::Algorithm = frodo_kem_rs::Algorithm::$alg;
+            }
+        )*
+    };
+}
+
+frodo_params! {
+    FrodoKem640AesParams => FrodoKem640Aes,
+    FrodoKem976AesParams => FrodoKem976Aes,
+    FrodoKem1344AesParams => FrodoKem1344Aes,
+    FrodoKem640ShakeParams => FrodoKem640Shake,
+    FrodoKem976ShakeParams => FrodoKem976Shake,
+    FrodoKem1344ShakeParams => FrodoKem1344Shake,
+}
+
+pub struct FrodoKem<P>(PhantomData<P>);
+
+pub type FrodoKem640Aes = FrodoKem<FrodoKem640AesParams>;
+pub type FrodoKem976Aes = FrodoKem<FrodoKem976AesParams>;
+pub type FrodoKem1344Aes = FrodoKem<FrodoKem1344AesParams>;
+pub type FrodoKem640Shake = FrodoKem<FrodoKem640ShakeParams>;
+pub type FrodoKem976Shake = FrodoKem<FrodoKem976ShakeParams>;
+pub type FrodoKem1344Shake = FrodoKem<FrodoKem1344ShakeParams>;
+
+impl<P: FrodoParams> BaseKem for FrodoKem<P> {
+    type DecapsulationKey = frodo_kem_rs::DecryptionKey;
+    type EncapsulationKey = frodo_kem_rs::EncryptionKey;
+    type Ciphertext = frodo_kem_rs::Ciphertext;
+    type SharedSecret = frodo_kem_rs::SharedSecret;
+
+    fn generate(rng: &mut impl CryptoRngCore) -> (Self::DecapsulationKey, Self::EncapsulationKey) {
+        let (ek, dk) = P::ALGORITHM.generate_keypair(rng);
+        (dk, ek)
+    }
+
+    fn encap(
+        rng: &mut impl CryptoRngCore,
+        ek: &Self::EncapsulationKey,
+    ) -> (Self::Ciphertext, Self::SharedSecret) {
+        P::ALGORITHM.encapsulate_with_rng(ek, rng).unwrap()
+    }
+
+    fn decap(dk: &Self::DecapsulationKey, ct: &Self::Ciphertext) -> Self::SharedSecret {
+        P::ALGORITHM.decapsulate(dk, ct).unwrap().0
+    }
+}
+
+// KEMs wrapped from PQClean by the `pqcrypto` crates.  These draw their randomness from the
+// operating system, so the `rng` argument is ignored.
+#[derive(Clone)]
+pub struct PqCleanEncapsulationKey<T>(T);
+
+impl<T: pqcrypto_traits::kem::PublicKey> AsRef<[u8]> for PqCleanEncapsulationKey<T> {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+pub struct PqCleanCiphertext<T>(T);
+
+impl<T: pqcrypto_traits::kem::Ciphertext> AsRef<[u8]> for PqCleanCiphertext<T> {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_bytes()
     }
 }
 
-// Classic McEliece
+pub struct PqCleanSharedSecret<T>(T);
+
+impl<T: pqcrypto_traits::kem::SharedSecret> AsRef<[u8]> for PqCleanSharedSecret<T> {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+macro_rules! pqclean_kem {
+    ($name:ident, $($kem:ident)::+) => {
+        pub struct $name;
+
+        impl BaseKem for $name {
+            type DecapsulationKey = $($kem)::+::SecretKey;
+            type EncapsulationKey = PqCleanEncapsulationKey<$($kem)::+::PublicKey>;
+            type Ciphertext = PqCleanCiphertext<$($kem)::+::Ciphertext>;
+            type SharedSecret = PqCleanSharedSecret<$($kem)::+::SharedSecret>;
+
+            fn generate(
+                _rng: &mut impl CryptoRngCore,
+            ) -> (Self::DecapsulationKey, Self::EncapsulationKey) {
+                let (ek, dk) = $($kem)::+::keypair();
+                (dk, PqCleanEncapsulationKey(ek))
+            }
+
+            fn encap(
+                _rng: &mut impl CryptoRngCore,
+                ek: &Self::EncapsulationKey,
+            ) -> (Self::Ciphertext, Self::SharedSecret) {
+                let (ss, ct) = $($kem)::+::encapsulate(&ek.0);
+                (PqCleanCiphertext(ct), PqCleanSharedSecret(ss))
+            }
+
+            fn decap(dk: &Self::DecapsulationKey, ct: &Self::Ciphertext) -> Self::SharedSecret {
+                PqCleanSharedSecret($($kem)::+::decapsulate(&ct.0, dk))
+            }
+        }
+
+        impl DecodeKem for $name {
+            const EK_LEN: usize = $($kem)::+::public_key_bytes();
+            const CT_LEN: usize = $($kem)::+::ciphertext_bytes();
+
+            fn decode_ek(bytes: &[u8]) -> Result<Self::EncapsulationKey, DecodeError> {
+                let ek = pqcrypto_traits::kem::PublicKey::from_bytes(bytes).map_err(|_| DecodeError)?;
+                Ok(PqCleanEncapsulationKey(ek))
+            }
+
+            fn decode_ct(bytes: &[u8]) -> Result<Self::Ciphertext, DecodeError> {
+                let ct = pqcrypto_traits::kem::Ciphertext::from_bytes(bytes).map_err(|_| DecodeError)?;
+                Ok(PqCleanCiphertext(ct))
+            }
+        }
+    };
+}
+
+// HQC
+pqclean_kem!(Hqc128, pqcrypto_hqc::hqc128);
+pqclean_kem!(Hqc192, pqcrypto_hqc::hqc192);
+pqclean_kem!(Hqc256, pqcrypto_hqc::hqc256);
+
+// Streamlined NTRU Prime
+pqclean_kem!(Sntrup761, pqcrypto_ntruprime::sntrup761);
+
+// Classic McEliece, with the parameter set chosen by the `mceliece*` cargo features
 pub type McElieceDecapsulationKey = classic_mceliece_rust::SecretKey<'static>;
 
 pub struct McElieceEncapsulationKey(classic_mceliece_rust::PublicKey<'static>);
@@ -120,6 +848,36 @@ impl AsRef<[u8]> for McElieceEncapsulationKey {
 
 pub struct ClassicMcEliece;
 
+macro_rules! mceliece_parameter_sets {
+    ($($feature:literal => $alias:ident,)*) => {
+        $(
+            #[cfg(feature = $feature)]
+            impl ClassicMcEliece {
+                pub const PARAMETER_SET: &str = $feature;
+            }
+
+            #[cfg(feature = $feature)]
+            pub type $alias = ClassicMcEliece;
+        )*
+
+        #[cfg(not(any($(feature = $feature),*)))]
+        compile_error!("exactly one Classic McEliece parameter set feature must be enabled");
+    };
+}
+
+mceliece_parameter_sets! {
+    "mceliece348864" => ClassicMcEliece348864,
+    "mceliece348864f" => ClassicMcEliece348864F,
+    "mceliece460896" => ClassicMcEliece460896,
+    "mceliece460896f" => ClassicMcEliece460896F,
+    "mceliece6688128" => ClassicMcEliece6688128,
+    "mceliece6688128f" => ClassicMcEliece6688128F,
+    "mceliece6960119" => ClassicMcEliece6960119,
+    "mceliece6960119f" => ClassicMcEliece6960119F,
+    "mceliece8192128" => ClassicMcEliece8192128,
+    "mceliece8192128f" => ClassicMcEliece8192128F,
+}
+
 impl BaseKem for ClassicMcEliece {
     type DecapsulationKey = McElieceDecapsulationKey;
     type EncapsulationKey = McElieceEncapsulationKey;
@@ -139,13 +897,70 @@ impl BaseKem for ClassicMcEliece {
     }
 
     fn decap(dk: &Self::DecapsulationKey, ct: &Self::Ciphertext) -> Self::SharedSecret {
-        classic_mceliece_rust::decapsulate_boxed(&ct, &dk)
+        classic_mceliece_rust::decapsulate_boxed(ct, dk)
+    }
+}
+
+// An RNG that replays a fixed byte string, and panics if more is asked of it.  This lets us supply
+// the exact seed that Classic McEliece key generation reads from its RNG.
+struct SeedRng<'a>(&'a [u8]);
+
+impl RngCore for SeedRng<'_> {
+    fn next_u32(&mut self) -> u32 {
+        rand_core::impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        rand_core::impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let (head, tail) = self.0.split_at(dest.len());
+        dest.copy_from_slice(head);
+        self.0 = tail;
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for SeedRng<'_> {}
+
+// The seed is the 32-byte seed delta from which key generation is derived
+impl SeedKem for ClassicMcEliece {
+    const ID: &'static [u8] = Self::PARAMETER_SET.as_bytes();
+    const SEED_LEN: usize = 32;
+
+    fn generate_from_seed(seed: &[u8]) -> (Self::DecapsulationKey, Self::EncapsulationKey) {
+        assert_eq!(seed.len(), Self::SEED_LEN);
+        let (ek, dk) = classic_mceliece_rust::keypair_boxed(&mut SeedRng(seed));
+        (dk, McElieceEncapsulationKey(ek))
+    }
+}
+
+// Classic McEliece samples its error vector by rejection, so it does not consume a fixed amount
+// of randomness.  Instead, the randomness is used as the seed for a ChaCha20 RNG.
+impl DerandKem for ClassicMcEliece {
+    const ENCAP_RANDOMNESS_LEN: usize = 32;
+
+    fn encap_derand(
+        ek: &Self::EncapsulationKey,
+        randomness: &[u8],
+    ) -> (Self::Ciphertext, Self::SharedSecret) {
+        let seed: [u8; 32] = randomness.try_into().unwrap();
+        let mut rng = ChaCha20Rng::from_seed(seed);
+        classic_mceliece_rust::encapsulate_boxed(&ek.0, &mut rng)
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use hex_literal::hex;
+    use rand::{rngs::StdRng, RngCore, SeedableRng};
+    use serde::Deserialize;
 
     fn test_encap_decap<K: BaseKem>() {
         let mut rng = rand::thread_rng();
@@ -155,18 +970,424 @@ mod test {
         assert_eq!(ss_e.as_ref(), ss_d.as_ref());
     }
 
+    fn test_generate_from_seed<K: SeedKem>() {
+        let mut rng = rand::thread_rng();
+        let mut seed = vec![0u8; K::SEED_LEN];
+        rng.fill_bytes(&mut seed);
+
+        let (dk, ek_1) = K::generate_from_seed(&seed);
+        let (_dk, ek_2) = K::generate_from_seed(&seed);
+        assert_eq!(ek_1.as_ref(), ek_2.as_ref());
+
+        let (ct, ss_e) = K::encap(&mut rng, &ek_1);
+        let ss_d = K::decap(&dk, &ct);
+        assert_eq!(ss_e.as_ref(), ss_d.as_ref());
+    }
+
+    fn test_encap_derand<K: DerandKem>() {
+        let mut rng = rand::thread_rng();
+        let (dk, ek) = K::generate(&mut rng);
+
+        let mut randomness = vec![0u8; K::ENCAP_RANDOMNESS_LEN];
+        rng.fill_bytes(&mut randomness);
+
+        let (ct_1, ss_1) = K::encap_derand(&ek, &randomness);
+        let (ct_2, ss_2) = K::encap_derand(&ek, &randomness);
+        assert_eq!(ct_1.as_ref(), ct_2.as_ref());
+        assert_eq!(ss_1.as_ref(), ss_2.as_ref());
+
+        let ss_d = K::decap(&dk, &ct_1);
+        assert_eq!(ss_1.as_ref(), ss_d.as_ref());
+    }
+
     #[test]
     fn x25519() {
         test_encap_decap::<X25519>();
     }
 
     #[test]
-    fn ml_kem() {
-        test_encap_decap::<MlKem>();
+    fn x25519_from_seed() {
+        test_generate_from_seed::<X25519>();
+    }
+
+    #[test]
+    fn x25519_derand() {
+        test_encap_derand::<X25519>();
+    }
+
+    fn test_decode<K: DecodeKem>() {
+        let mut rng = rand::thread_rng();
+        let (dk, ek) = K::generate(&mut rng);
+        let (ct, ss_e) = K::encap(&mut rng, &ek);
+
+        let ek_bytes = ek.as_ref();
+        assert_eq!(ek_bytes.len(), K::EK_LEN);
+        assert_eq!(K::decode_ek(ek_bytes).unwrap().as_ref(), ek_bytes);
+        assert!(K::decode_ek(&ek_bytes[1..]).is_err());
+
+        let ct_bytes = ct.as_ref();
+        assert_eq!(ct_bytes.len(), K::CT_LEN);
+        let ss_d = K::decap(&dk, &K::decode_ct(ct_bytes).unwrap());
+        assert_eq!(ss_e.as_ref(), ss_d.as_ref());
+        assert!(K::decode_ct(&ct_bytes[1..]).is_err());
+    }
+
+    #[test]
+    fn x25519_decode() {
+        test_decode::<X25519>();
+    }
+
+    #[test]
+    fn x448() {
+        test_encap_decap::<X448>();
+    }
+
+    #[test]
+    fn ml_kem_from_seed() {
+        test_generate_from_seed::<MlKem512>();
+        test_generate_from_seed::<MlKem768>();
+        test_generate_from_seed::<MlKem1024>();
+    }
+
+    #[test]
+    fn ml_kem_decode() {
+        test_decode::<MlKem512>();
+        test_decode::<MlKem768>();
+        test_decode::<MlKem1024>();
+    }
+
+    // A coefficient of q = 3329 = 0xd01 fails the modulus check
+    #[test]
+    fn ml_kem_rejects_unreduced_key() {
+        let mut rng = rand::thread_rng();
+        let (_dk, ek) = MlKem768::generate(&mut rng);
+
+        let mut ek_bytes = ek.as_ref().to_vec();
+        ek_bytes[0] = 0x01;
+        ek_bytes[1] = (ek_bytes[1] & 0xf0) | 0x0d;
+        assert!(MlKem768::decode_ek(&ek_bytes).is_err());
+
+        ek_bytes[0] = 0x00;
+        assert!(MlKem768::decode_ek(&ek_bytes).is_ok());
+    }
+
+    #[test]
+    fn ml_kem_derand() {
+        test_encap_derand::<MlKem512>();
+        test_encap_derand::<MlKem768>();
+        test_encap_derand::<MlKem1024>();
+    }
+
+    #[test]
+    fn p256() {
+        test_encap_decap::<P256>();
+        test_decode::<P256>();
+    }
+
+    #[test]
+    fn p384() {
+        test_encap_decap::<P384>();
+        test_decode::<P384>();
+    }
+
+    #[test]
+    fn p521() {
+        test_encap_decap::<P521>();
+        test_decode::<P521>();
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid ciphertext")]
+    fn p256_rejects_invalid_point() {
+        let mut rng = rand::thread_rng();
+        let (dk, _ek) = P256::generate(&mut rng);
+
+        // (1, 1) is not on the curve
+        let mut x = FieldBytes::<p256::NistP256>::default();
+        x[31] = 1;
+        let ct = EncodedPoint::<p256::NistP256>::from_affine_coordinates(&x, &x, false);
+        P256::decap(&dk, &ct);
+    }
+
+    #[test]
+    fn p256_decode_rejects_invalid_points() {
+        let mut rng = rand::thread_rng();
+        let (_dk, ek) = P256::generate(&mut rng);
+
+        let mut x = FieldBytes::<p256::NistP256>::default();
+        x[31] = 1;
+        let point = EncodedPoint::<p256::NistP256>::from_affine_coordinates(&x, &x, false);
+        assert!(P256::decode_ek(point.as_bytes()).is_err());
+
+        // Compressed points are not accepted
+        let compressed = PublicKey::<p256::NistP256>::from_encoded_point(&ek)
+            .unwrap()
+            .to_encoded_point(true);
+        assert!(P256::decode_ek(compressed.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn rsa_kem_2048() {
+        test_encap_decap::<RsaKem2048>();
+    }
+
+    #[test]
+    fn rsa_kem_3072() {
+        test_encap_decap::<RsaKem3072>();
+    }
+
+    #[test]
+    fn rsa_kem_4096() {
+        test_encap_decap::<RsaKem4096>();
+    }
+
+    // The one-step KDF example from RFC 7518, Appendix C, which derives a JWE content encryption
+    // key with the same construction as KDF3
+    #[test]
+    fn kdf3_rfc7518() {
+        let z = hex!("9e56d91d817135d372834283bf84269cfb316ea3da806a48f6daa7798cfe90c4");
+        let other_info = hex!("000000074131323847434d00000005416c69636500000003426f6200000080");
+
+        let mut key = [0u8; 16];
+        RsaKem2048::kdf3(&z, &other_info, &mut key);
+        assert_eq!(key, hex!("56aa8deaf8236d205c2228cd71a7101a"));
+    }
+
+    #[derive(Deserialize)]
+    struct RsaKemTestVector {
+        #[serde(with = "hex::serde")]
+        n: Vec<u8>,
+        e: u32,
+        #[serde(with = "hex::serde")]
+        d: Vec<u8>,
+        #[serde(with = "hex::serde")]
+        p: Vec<u8>,
+        #[serde(with = "hex::serde")]
+        q: Vec<u8>,
+        #[serde(with = "hex::serde")]
+        z: Vec<u8>,
+        #[serde(with = "hex::serde")]
+        ciphertext: Vec<u8>,
+        #[serde(with = "hex::serde")]
+        shared_secret: Vec<u8>,
+    }
+
+    // There are no published RSA-KEM vectors with KDF3; these were computed with Python's
+    // `cryptography` and `hashlib`, using z as the RSASVE secret
+    #[test]
+    fn rsa_kem_2048_known_answer() {
+        let tvs: Vec<RsaKemTestVector> =
+            serde_json::from_str(include_str!("../test-vectors/rsa-kem.json")).unwrap();
+
+        for tv in tvs {
+            let dk = RsaPrivateKey::from_components(
+                BigUint::from_bytes_be(&tv.n),
+                BigUint::from(tv.e),
+                BigUint::from_bytes_be(&tv.d),
+                vec![BigUint::from_bytes_be(&tv.p), BigUint::from_bytes_be(&tv.q)],
+            )
+            .unwrap();
+            let ek = RsaKemEncapsulationKey::new(dk.to_public_key());
+
+            let (ct, ss) = RsaKem2048::encap(&mut SeedRng(&tv.z), &ek);
+            assert_eq!(ct, tv.ciphertext);
+            assert_eq!(ss.as_slice(), &tv.shared_secret);
+
+            let ss = RsaKem2048::decap(&dk, &tv.ciphertext);
+            assert_eq!(ss.as_slice(), &tv.shared_secret);
+        }
+    }
+
+    #[test]
+    fn dhkem_x25519_hkdf_sha256() {
+        test_encap_decap::<DhkemX25519HkdfSha256>();
+    }
+
+    #[test]
+    fn dhkem_p256_hkdf_sha256() {
+        test_encap_decap::<DhkemP256HkdfSha256>();
+    }
+
+    // RFC 9180, Appendix A.1.1
+    #[test]
+    fn dhkem_x25519_hkdf_sha256_rfc9180() {
+        let sk_rm = hex!("4612c550263fc8ad58375df3f557aac531d26850903e55a9f23f21d8534e8ac8");
+        let pk_rm = hex!("3948cfe0ad1ddb695d780e59077195da6c56506b027329794ab02bca80815c4d");
+        let enc = hex!("37fda3567bdbd628e88668c3c8d7e97d1d1253b6d4ea6d44c150f741f1bf4431");
+        let shared_secret =
+            hex!("fe0e18c9f024ce43799ae393c7e8fe8fce9d218875e8227b0187c04e7d2ea1fc");
+
+        let dk = HpkeDhkemDecapsulationKey::<X25519> {
+            dk: x25519_dalek::StaticSecret::from(sk_rm),
+            ek: x25519_dalek::PublicKey::from(pk_rm),
+        };
+        let ct = x25519_dalek::PublicKey::from(enc);
+        let ss = DhkemX25519HkdfSha256::decap(&dk, &ct);
+        assert_eq!(ss.as_slice(), &shared_secret);
+    }
+
+    // RFC 9180, Appendix A.3.1
+    #[test]
+    fn dhkem_p256_hkdf_sha256_rfc9180() {
+        let sk_rm = hex!("f3ce7fdae57e1a310d87f1ebbde6f328be0a99cdbcadf4d6589cf29de4b8ffd2");
+        let pk_rm = hex!(
+            "04fe8c19ce0905191ebc298a9245792531f26f0cece2460639e8bc39cb7f706a"
+            "826a779b4cf969b8a0e539c7f62fb3d30ad6aa8f80e30f1d128aafd68a2ce72ea0"
+        );
+        let enc = hex!(
+            "04a92719c6195d5085104f469a8b9814d5838ff72b60501e2c4466e5e67b325a"
+            "c98536d7b61a1af4b78e5b7f951c0900be863c403ce65c9bfcb9382657222d18c4"
+        );
+        let shared_secret =
+            hex!("c0d26aeab536609a572b07695d933b589dcf363ff9d93c93adea537aeabb8cb8");
+
+        let dk = HpkeDhkemDecapsulationKey::<P256> {
+            dk: NonZeroScalar::try_from(sk_rm.as_slice()).unwrap(),
+            ek: EncodedPoint::<p256::NistP256>::from_bytes(pk_rm).unwrap(),
+        };
+        let ct = EncodedPoint::<p256::NistP256>::from_bytes(enc).unwrap();
+        let ss = DhkemP256HkdfSha256::decap(&dk, &ct);
+        assert_eq!(ss.as_slice(), &shared_secret);
+    }
+
+    #[test]
+    fn ml_kem_512() {
+        test_encap_decap::<MlKem512>();
+    }
+
+    #[test]
+    fn ml_kem_768() {
+        test_encap_decap::<MlKem768>();
+    }
+
+    #[test]
+    fn ml_kem_1024() {
+        test_encap_decap::<MlKem1024>();
+    }
+
+    #[test]
+    fn kyber768_round3() {
+        test_encap_decap::<Kyber768Round3>();
+    }
+
+    // The first entry of the round-3 KAT file, PQCkemKAT_2400.rsp.  The RNG output is that of the
+    // NIST AES-256 CTR DRBG seeded with `seed`, for the three 32-byte randombytes() calls made by
+    // keygen and encapsulation.  The entry is checked against its SHA-256, as recorded in the
+    // `nistkat-sha256` field of PQClean's META.yml and the `single` field of liboqs's kats.json.
+    #[test]
+    fn kyber768_round3_kat() {
+        let seed = hex!(
+            "061550234d158c5ec95595fe04ef7a25767f2e24cc2bc479d09d86dc9abcfde7"
+            "056a8c266f9ef97ed08541dbd2e1ffa1"
+        );
+        let drbg_output = hex!(
+            "7c9935a0b07694aa0c6d10e4db6b1add2fd81a25ccb148032dcd739936737f2d"
+            "8626ed79d451140800e03b59b956f8210e556067407d13dc90fa9e8b872bfb8f"
+            "147c03f7a5bebba406c8fae1874d7f13c80efe79a3a9a874cc09fe76f6997615"
+        );
+
+        let mut rng = SeedRng(&drbg_output);
+        let (dk, ek) = Kyber768Round3::generate(&mut rng);
+        let (ct, ss) = Kyber768Round3::encap(&mut rng, &ek);
+        assert!(rng.0.is_empty());
+        assert_eq!(Kyber768Round3::decap(&dk, &ct), ss);
+
+        let entry = format!(
+            "count = 0\nseed = {}\npk = {}\nsk = {}\nct = {}\nss = {}\n",
+            hex::encode_upper(seed),
+            hex::encode_upper(ek),
+            hex::encode_upper(dk),
+            hex::encode_upper(ct),
+            hex::encode_upper(ss),
+        );
+        assert_eq!(
+            Sha256::digest(entry.as_bytes()).as_slice(),
+            hex!("89e82a5bf2d4ddb2c6444e10409e6d9ca65dafbca67d1a0db2c9b54920a29172")
+        );
+    }
+
+    // Both KEMs consume the same 64 bytes of key generation randomness and 32 bytes of
+    // encapsulation randomness, and produce ciphertexts of the same size
+    #[test]
+    fn kyber768_round3_is_not_ml_kem_768() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let (_dk, ek) = Kyber768Round3::generate(&mut rng);
+        let (ct_kyber, ss_kyber) = Kyber768Round3::encap(&mut rng, &ek);
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let (_dk, ek) = MlKem768::generate(&mut rng);
+        let (ct_ml_kem, ss_ml_kem) = MlKem768::encap(&mut rng, &ek);
+
+        assert_eq!(ct_kyber.len(), ct_ml_kem.len());
+        assert_ne!(ss_kyber.as_slice(), ss_ml_kem.as_slice());
+    }
+
+    #[test]
+    fn frodo_kem_640_aes() {
+        test_encap_decap::<FrodoKem640Aes>();
+    }
+
+    #[test]
+    fn frodo_kem_976_aes() {
+        test_encap_decap::<FrodoKem976Aes>();
+    }
+
+    #[test]
+    fn frodo_kem_1344_aes() {
+        test_encap_decap::<FrodoKem1344Aes>();
+    }
+
+    #[test]
+    fn frodo_kem_640_shake() {
+        test_encap_decap::<FrodoKem640Shake>();
+    }
+
+    #[test]
+    fn frodo_kem_976_shake() {
+        test_encap_decap::<FrodoKem976Shake>();
+    }
+
+    #[test]
+    fn frodo_kem_1344_shake() {
+        test_encap_decap::<FrodoKem1344Shake>();
+    }
+
+    #[test]
+    fn hqc_128() {
+        test_encap_decap::<Hqc128>();
+        test_decode::<Hqc128>();
+    }
+
+    #[test]
+    fn hqc_192() {
+        test_encap_decap::<Hqc192>();
+        test_decode::<Hqc192>();
+    }
+
+    #[test]
+    fn hqc_256() {
+        test_encap_decap::<Hqc256>();
+        test_decode::<Hqc256>();
+    }
+
+    #[test]
+    fn sntrup761() {
+        test_encap_decap::<Sntrup761>();
+        test_decode::<Sntrup761>();
     }
 
     #[test]
     fn classic_mceliece() {
         test_encap_decap::<ClassicMcEliece>();
     }
+
+    #[test]
+    fn classic_mceliece_from_seed() {
+        test_generate_from_seed::<ClassicMcEliece>();
+    }
+
+    #[test]
+    fn classic_mceliece_derand() {
+        test_encap_derand::<ClassicMcEliece>();
+    }
 }
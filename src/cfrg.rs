@@ -0,0 +1,359 @@
+use std::marker::PhantomData;
+
+use rand_core::CryptoRngCore;
+use sha3::{
+    digest::{ExtendableOutput, Update, XofReader},
+    Shake256,
+};
+
+use crate::base::{
+    BaseKem, DecodeError, DecodeKem, DerandKem, MlKem1024, MlKem768, SeedKem, P256, P384, X25519,
+};
+use crate::combiners::{Combiner, Qsf, QSF_P256_MLKEM768, QSF_P384_MLKEM1024, QSF_X25519_MLKEM768};
+use crate::hybrid::{Ciphertext, DecapsulationKey, EncapsulationKey, SharedSecret};
+
+// The named QSF instantiations from draft-irtf-cfrg-concrete-hybrid-kems.  These fix the key and
+// ciphertext encodings on top of the QSF combiner:
+//
+// * Decapsulation keys are a 32-byte seed, expanded with SHAKE256 into seed_PQ || seed_T
+// * Encapsulation keys are ek_PQ || ek_T
+// * Ciphertexts are ct_PQ || ct_T
+// * Encapsulation randomness is split the same way, PQ first
+pub trait QsfParams {
+    type T: SeedKem + DerandKem + DecodeKem;
+    type PQ: SeedKem + DerandKem + DecodeKem;
+
+    const ID: &'static [u8];
+    const COMBINER: Qsf;
+}
+
+pub struct QsfX25519MlKem768Params;
+
+impl QsfParams for QsfX25519MlKem768Params {
+    type T = X25519;
+    type PQ = MlKem768;
+
+    const ID: &'static [u8] = b"QSF-X25519-MLKEM768";
+    const COMBINER: Qsf = QSF_X25519_MLKEM768;
+}
+
+pub struct QsfP256MlKem768Params;
+
+impl QsfParams for QsfP256MlKem768Params {
+    type T = P256;
+    type PQ = MlKem768;
+
+    const ID: &'static [u8] = b"QSF-P256-MLKEM768";
+    const COMBINER: Qsf = QSF_P256_MLKEM768;
+}
+
+pub struct QsfP384MlKem1024Params;
+
+impl QsfParams for QsfP384MlKem1024Params {
+    type T = P384;
+    type PQ = MlKem1024;
+
+    const ID: &'static [u8] = b"QSF-P384-MLKEM1024";
+    const COMBINER: Qsf = QSF_P384_MLKEM1024;
+}
+
+pub struct QsfKem<P>(PhantomData<P>);
+
+pub type QsfX25519MlKem768 = QsfKem<QsfX25519MlKem768Params>;
+pub type QsfP256MlKem768 = QsfKem<QsfP256MlKem768Params>;
+pub type QsfP384MlKem1024 = QsfKem<QsfP384MlKem1024Params>;
+
+pub struct QsfDecapsulationKey<P: QsfParams> {
+    seed: [u8; 32],
+    dk: DecapsulationKey<P::T, P::PQ>,
+}
+
+impl<P: QsfParams> QsfDecapsulationKey<P> {
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.seed
+    }
+}
+
+pub struct QsfEncapsulationKey<P: QsfParams> {
+    ek: EncapsulationKey<P::T, P::PQ>,
+    ek_bytes: Vec<u8>,
+}
+
+impl<P: QsfParams> Clone for QsfEncapsulationKey<P> {
+    fn clone(&self) -> Self {
+        Self {
+            ek: self.ek.clone(),
+            ek_bytes: self.ek_bytes.clone(),
+        }
+    }
+}
+
+impl<P: QsfParams> AsRef<[u8]> for QsfEncapsulationKey<P> {
+    fn as_ref(&self) -> &[u8] {
+        &self.ek_bytes
+    }
+}
+
+// Parses ek_PQ || ek_T
+impl<P: QsfParams> TryFrom<&[u8]> for QsfEncapsulationKey<P> {
+    type Error = DecodeError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, DecodeError> {
+        if bytes.len() != P::PQ::EK_LEN + P::T::EK_LEN {
+            return Err(DecodeError);
+        }
+        let (ek_pq, ek_t) = bytes.split_at(P::PQ::EK_LEN);
+        let ek = EncapsulationKey {
+            t: P::T::decode_ek(ek_t)?,
+            pq: P::PQ::decode_ek(ek_pq)?,
+        };
+        Ok(Self {
+            ek,
+            ek_bytes: bytes.to_vec(),
+        })
+    }
+}
+
+// The component ciphertexts are kept alongside the encoding
+pub struct QsfCiphertext<P: QsfParams> {
+    ct: Ciphertext<P::T, P::PQ>,
+    ct_bytes: Vec<u8>,
+}
+
+impl<P: QsfParams> AsRef<[u8]> for QsfCiphertext<P> {
+    fn as_ref(&self) -> &[u8] {
+        &self.ct_bytes
+    }
+}
+
+// Parses ct_PQ || ct_T
+impl<P: QsfParams> TryFrom<&[u8]> for QsfCiphertext<P> {
+    type Error = DecodeError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, DecodeError> {
+        if bytes.len() != P::PQ::CT_LEN + P::T::CT_LEN {
+            return Err(DecodeError);
+        }
+        let (ct_pq, ct_t) = bytes.split_at(P::PQ::CT_LEN);
+        let ct = Ciphertext {
+            t: P::T::decode_ct(ct_t)?,
+            pq: P::PQ::decode_ct(ct_pq)?,
+        };
+        Ok(Self {
+            ct,
+            ct_bytes: bytes.to_vec(),
+        })
+    }
+}
+
+impl<P: QsfParams> QsfKem<P> {
+    fn expand_decapsulation_key(seed: [u8; 32]) -> QsfDecapsulationKey<P> {
+        let mut h = Shake256::default();
+        h.update(&seed);
+        let mut reader = h.finalize_xof();
+
+        let mut seed_pq = vec![0u8; P::PQ::SEED_LEN];
+        let mut seed_t = vec![0u8; P::T::SEED_LEN];
+        reader.read(&mut seed_pq);
+        reader.read(&mut seed_t);
+
+        let (dk_pq, ek_pq) = P::PQ::generate_from_seed(&seed_pq);
+        let (dk_t, ek_t) = P::T::generate_from_seed(&seed_t);
+
+        let ek = EncapsulationKey { t: ek_t, pq: ek_pq };
+        let dk = DecapsulationKey {
+            t: dk_t,
+            pq: dk_pq,
+            ek,
+        };
+        QsfDecapsulationKey { seed, dk }
+    }
+
+    fn combine(
+        ct: &Ciphertext<P::T, P::PQ>,
+        ek: &EncapsulationKey<P::T, P::PQ>,
+        ss_t: &[u8],
+        ss_pq: &[u8],
+    ) -> SharedSecret {
+        P::COMBINER.combine(
+            ss_t,
+            ct.t.as_ref(),
+            ek.t.as_ref(),
+            ss_pq,
+            ct.pq.as_ref(),
+            ek.pq.as_ref(),
+        )
+    }
+}
+
+impl<P: QsfParams> BaseKem for QsfKem<P> {
+    type DecapsulationKey = QsfDecapsulationKey<P>;
+    type EncapsulationKey = QsfEncapsulationKey<P>;
+    type Ciphertext = QsfCiphertext<P>;
+    type SharedSecret = SharedSecret;
+
+    fn generate(rng: &mut impl CryptoRngCore) -> (Self::DecapsulationKey, Self::EncapsulationKey) {
+        let mut seed = [0u8; 32];
+        rng.fill_bytes(&mut seed);
+        Self::generate_from_seed(&seed)
+    }
+
+    fn encap(
+        rng: &mut impl CryptoRngCore,
+        ek: &Self::EncapsulationKey,
+    ) -> (Self::Ciphertext, Self::SharedSecret) {
+        let mut randomness = vec![0u8; Self::ENCAP_RANDOMNESS_LEN];
+        rng.fill_bytes(&mut randomness);
+        Self::encap_derand(ek, &randomness)
+    }
+
+    fn decap(dk: &Self::DecapsulationKey, ct: &Self::Ciphertext) -> Self::SharedSecret {
+        let ss_pq = P::PQ::decap(&dk.dk.pq, &ct.ct.pq);
+        let ss_t = P::T::decap(&dk.dk.t, &ct.ct.t);
+        Self::combine(&ct.ct, &dk.dk.ek, ss_t.as_ref(), ss_pq.as_ref())
+    }
+}
+
+impl<P: QsfParams> SeedKem for QsfKem<P> {
+    const ID: &'static [u8] = P::ID;
+    const SEED_LEN: usize = 32;
+
+    fn generate_from_seed(seed: &[u8]) -> (Self::DecapsulationKey, Self::EncapsulationKey) {
+        assert_eq!(seed.len(), Self::SEED_LEN);
+        let dk = Self::expand_decapsulation_key(seed.try_into().unwrap());
+        let ek_bytes = [dk.dk.ek.pq.as_ref(), dk.dk.ek.t.as_ref()].concat();
+        let ek = QsfEncapsulationKey {
+            ek: dk.dk.ek.clone(),
+            ek_bytes,
+        };
+        (dk, ek)
+    }
+}
+
+impl<P: QsfParams> DecodeKem for QsfKem<P> {
+    const EK_LEN: usize = P::PQ::EK_LEN + P::T::EK_LEN;
+    const CT_LEN: usize = P::PQ::CT_LEN + P::T::CT_LEN;
+
+    fn decode_ek(bytes: &[u8]) -> Result<Self::EncapsulationKey, DecodeError> {
+        QsfEncapsulationKey::try_from(bytes)
+    }
+
+    fn decode_ct(bytes: &[u8]) -> Result<Self::Ciphertext, DecodeError> {
+        QsfCiphertext::try_from(bytes)
+    }
+}
+
+impl<P: QsfParams> DerandKem for QsfKem<P> {
+    const ENCAP_RANDOMNESS_LEN: usize = P::PQ::ENCAP_RANDOMNESS_LEN + P::T::ENCAP_RANDOMNESS_LEN;
+
+    fn encap_derand(
+        ek: &Self::EncapsulationKey,
+        randomness: &[u8],
+    ) -> (Self::Ciphertext, Self::SharedSecret) {
+        assert_eq!(randomness.len(), Self::ENCAP_RANDOMNESS_LEN);
+        let (randomness_pq, randomness_t) = randomness.split_at(P::PQ::ENCAP_RANDOMNESS_LEN);
+
+        let (ct_pq, ss_pq) = P::PQ::encap_derand(&ek.ek.pq, randomness_pq);
+        let (ct_t, ss_t) = P::T::encap_derand(&ek.ek.t, randomness_t);
+
+        let ct = Ciphertext { t: ct_t, pq: ct_pq };
+        let ss = Self::combine(&ct, &ek.ek, ss_t.as_ref(), ss_pq.as_ref());
+        let ct_bytes = [ct.pq.as_ref(), ct.t.as_ref()].concat();
+        (QsfCiphertext { ct, ct_bytes }, ss)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::xwing::XWingKem;
+    use rand_core::RngCore;
+    use serde::Deserialize;
+    use std::collections::HashMap;
+
+    #[derive(Deserialize)]
+    struct TestVector {
+        #[serde(with = "hex::serde")]
+        seed: Vec<u8>,
+        #[serde(with = "hex::serde")]
+        randomness: Vec<u8>,
+        #[serde(with = "hex::serde")]
+        encapsulation_key: Vec<u8>,
+        #[serde(with = "hex::serde")]
+        decapsulation_key: Vec<u8>,
+        #[serde(with = "hex::serde")]
+        ciphertext: Vec<u8>,
+        #[serde(with = "hex::serde")]
+        shared_secret: Vec<u8>,
+    }
+
+    fn test_encap_decap<P: QsfParams>() {
+        let mut rng = rand::thread_rng();
+        let (dk, ek) = QsfKem::<P>::generate(&mut rng);
+        let (ct, ss_e) = QsfKem::<P>::encap(&mut rng, &ek);
+        let ss_d = QsfKem::<P>::decap(&dk, &ct);
+        assert_eq!(ss_e, ss_d);
+    }
+
+    // Test vectors for draft-irtf-cfrg-concrete-hybrid-kems, as distributed with the `hpke` crate
+    // (test-vectors/hybrid-defafa2.json).  There are no published vectors for GHP or PRE.
+    fn test_vectors<P: QsfParams>(name: &str) {
+        let mut all_tvs: HashMap<String, Vec<TestVector>> =
+            serde_json::from_str(include_str!("../test-vectors/cfrg-hybrid-kems.json")).unwrap();
+        let tvs = all_tvs.remove(name).unwrap();
+        assert!(!tvs.is_empty());
+
+        for tv in tvs {
+            let (dk, ek) = QsfKem::<P>::generate_from_seed(&tv.seed);
+            assert_eq!(dk.as_bytes().as_slice(), &tv.decapsulation_key);
+            assert_eq!(ek.as_ref(), &tv.encapsulation_key);
+
+            // Encapsulate to the published key and decapsulate the published ciphertext
+            let ek = QsfKem::<P>::decode_ek(&tv.encapsulation_key).unwrap();
+            let (ct, ss) = QsfKem::<P>::encap_derand(&ek, &tv.randomness);
+            assert_eq!(ct.as_ref(), &tv.ciphertext);
+            assert_eq!(ss.as_slice(), &tv.shared_secret);
+
+            let ct = QsfKem::<P>::decode_ct(&tv.ciphertext).unwrap();
+            let ss = QsfKem::<P>::decap(&dk, &ct);
+            assert_eq!(ss.as_slice(), &tv.shared_secret);
+        }
+    }
+
+    #[test]
+    fn x25519_ml_kem_768() {
+        test_encap_decap::<QsfX25519MlKem768Params>();
+        test_vectors::<QsfX25519MlKem768Params>("mlkem768_x25519");
+    }
+
+    #[test]
+    fn p256_ml_kem_768() {
+        test_encap_decap::<QsfP256MlKem768Params>();
+        test_vectors::<QsfP256MlKem768Params>("mlkem768_p256");
+    }
+
+    #[test]
+    fn p384_ml_kem_1024() {
+        test_encap_decap::<QsfP384MlKem1024Params>();
+        test_vectors::<QsfP384MlKem1024Params>("mlkem1024_p384");
+    }
+
+    // QSF-X25519-MLKEM768 is X-Wing
+    #[test]
+    fn x25519_ml_kem_768_is_xwing() {
+        let mut rng = rand::thread_rng();
+        let mut seed = [0u8; 32];
+        rng.fill_bytes(&mut seed);
+        let mut randomness = [0u8; 64];
+        rng.fill_bytes(&mut randomness);
+
+        let (_, ek_q) = QsfX25519MlKem768::generate_from_seed(&seed);
+        let (_, ek_x) = XWingKem::generate_from_seed(&seed);
+        assert_eq!(ek_q.as_ref(), ek_x.as_ref());
+
+        let (ct_q, ss_q) = QsfX25519MlKem768::encap_derand(&ek_q, &randomness);
+        let (ct_x, ss_x) = XWingKem::encap_derand(&ek_x, &randomness);
+        assert_eq!(ct_q.as_ref(), ct_x.as_slice());
+        assert_eq!(ss_q, ss_x);
+    }
+}
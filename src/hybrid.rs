@@ -1,7 +1,9 @@
-use crate::base::BaseKem;
+use crate::base::{BaseKem, DerandKem, SeedKem};
 use crate::combiners::{Combiner, NewPre};
 
+use hkdf::HkdfExtract;
 use rand_core::CryptoRngCore;
+use sha2::Sha256;
 use sha3::{digest::Output, Sha3_256};
 
 pub struct DecapsulationKey<T, PQ>
@@ -46,7 +48,8 @@ where
     pub pq: PQ::Ciphertext,
 }
 
-pub type SharedSecret = Output<Sha3_256>;
+// The shared secret of a hybrid KEM is the output of its combiner's hash function
+pub type SharedSecret<H = Sha3_256> = Output<H>;
 
 pub trait NewHybrid<T, PQ>
 where
@@ -86,12 +89,64 @@ where
     ) -> (DecapsulationKey<T, PQ>, EncapsulationKey<T, PQ>) {
         let (dk_t, ek_t) = T::generate(rng);
         let (dk_pq, ek_pq) = PQ::generate(rng);
+        Self::key_pair(dk_t, ek_t, dk_pq, ek_pq)
+    }
+
+    // Modeled on DeriveKeyPair from RFC 9180: The input keying material is extracted into a PRK,
+    // from which a seed for each component is expanded under its own label.  As HPKE does with its
+    // suite_id, every label is bound to the component algorithms, so that the same ikm yields
+    // unrelated key pairs for different hybrids.
+    pub fn derive_key_pair(ikm: &[u8]) -> (DecapsulationKey<T, PQ>, EncapsulationKey<T, PQ>)
+    where
+        T: SeedKem,
+        PQ: SeedKem,
+    {
+        const LABEL_PREFIX: &[u8] = b"hybrid-kem-v1";
+
+        // Each ID is length-prefixed so that the concatenation is unambiguous
+        let suite_id = [
+            &[T::ID.len() as u8][..],
+            T::ID,
+            &[PQ::ID.len() as u8][..],
+            PQ::ID,
+        ]
+        .concat();
+
+        let mut extract = HkdfExtract::<Sha256>::new(None);
+        extract.input_ikm(LABEL_PREFIX);
+        extract.input_ikm(&suite_id);
+        extract.input_ikm(b"dkp_prk");
+        extract.input_ikm(ikm);
+        let (_, dkp_prk) = extract.finalize();
+
+        let expand = |label: &[u8], len: usize| {
+            let mut seed = vec![0u8; len];
+            let length = (len as u16).to_be_bytes();
+            dkp_prk
+                .expand_multi_info(&[&length, LABEL_PREFIX, &suite_id, label], &mut seed)
+                .unwrap();
+            seed
+        };
+
+        let seed_t = expand(b"seed_t", T::SEED_LEN);
+        let seed_pq = expand(b"seed_pq", PQ::SEED_LEN);
+
+        let (dk_t, ek_t) = T::generate_from_seed(&seed_t);
+        let (dk_pq, ek_pq) = PQ::generate_from_seed(&seed_pq);
+        Self::key_pair(dk_t, ek_t, dk_pq, ek_pq)
+    }
 
+    fn key_pair(
+        dk_t: T::DecapsulationKey,
+        ek_t: T::EncapsulationKey,
+        dk_pq: PQ::DecapsulationKey,
+        ek_pq: PQ::EncapsulationKey,
+    ) -> (DecapsulationKey<T, PQ>, EncapsulationKey<T, PQ>) {
         let ek = EncapsulationKey { t: ek_t, pq: ek_pq };
         let dk = DecapsulationKey {
             t: dk_t,
             pq: dk_pq,
-            ek: (&ek).clone(),
+            ek: ek.clone(),
         };
         (dk, ek)
     }
@@ -100,7 +155,7 @@ where
         c: &C,
         rng: &mut impl CryptoRngCore,
         ek: &EncapsulationKey<T, PQ>,
-    ) -> (Ciphertext<T, PQ>, SharedSecret) {
+    ) -> (Ciphertext<T, PQ>, SharedSecret<C::Hash>) {
         let (ct_t, ss_t) = T::encap(rng, &ek.t);
         let (ct_pq, ss_pq) = PQ::encap(rng, &ek.pq);
 
@@ -116,11 +171,43 @@ where
         (ct, ss)
     }
 
+    // The randomness is split between the components, with the first
+    // `T::ENCAP_RANDOMNESS_LEN` bytes going to the traditional KEM and the remainder to the PQ KEM
+    pub fn encap_derand<C: Combiner>(
+        c: &C,
+        randomness: &[u8],
+        ek: &EncapsulationKey<T, PQ>,
+    ) -> (Ciphertext<T, PQ>, SharedSecret<C::Hash>)
+    where
+        T: DerandKem,
+        PQ: DerandKem,
+    {
+        assert_eq!(
+            randomness.len(),
+            T::ENCAP_RANDOMNESS_LEN + PQ::ENCAP_RANDOMNESS_LEN
+        );
+        let (randomness_t, randomness_pq) = randomness.split_at(T::ENCAP_RANDOMNESS_LEN);
+
+        let (ct_t, ss_t) = T::encap_derand(&ek.t, randomness_t);
+        let (ct_pq, ss_pq) = PQ::encap_derand(&ek.pq, randomness_pq);
+
+        let ct: Ciphertext<T, PQ> = Ciphertext { t: ct_t, pq: ct_pq };
+        let ss = c.combine(
+            ss_t.as_ref(),
+            ct.t.as_ref(),
+            ek.t.as_ref(),
+            ss_pq.as_ref(),
+            ct.pq.as_ref(),
+            ek.pq.as_ref(),
+        );
+        (ct, ss)
+    }
+
     pub fn decap<C: Combiner>(
         c: &C,
         dk: &DecapsulationKey<T, PQ>,
         ct: &Ciphertext<T, PQ>,
-    ) -> SharedSecret {
+    ) -> SharedSecret<C::Hash> {
         let ss_t = T::decap(&dk.t, &ct.t);
         let ss_pq = PQ::decap(&dk.pq, &ct.pq);
 
@@ -134,3 +221,17 @@ where
         )
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::{MlKem1024, MlKem768, X25519};
+
+    #[test]
+    fn derive_key_pair_binds_algorithms() {
+        let ikm = b"input keying material";
+        let (_dk, ek_768) = HybridKem::<X25519, MlKem768>::derive_key_pair(ikm);
+        let (_dk, ek_1024) = HybridKem::<X25519, MlKem1024>::derive_key_pair(ikm);
+        assert_ne!(ek_768.t.as_ref(), ek_1024.t.as_ref());
+    }
+}
@@ -1,6 +1,12 @@
 pub mod base;
+pub mod cfrg;
 pub mod combiners;
 pub mod hybrid;
+pub mod ssh;
+pub mod xwing;
+
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 
 #[cfg(test)]
 #[generic_tests::define]
@@ -8,6 +14,11 @@ mod tests {
     use crate::base::*;
     use crate::combiners::*;
     use crate::hybrid::*;
+    use crate::testing::*;
+    use blake2::Blake2b512;
+    use rand::RngCore;
+    use sha2::{Sha256, Sha384, Sha512};
+    use sha3::{digest::core_api::BlockSizeUser, Digest, Sha3_256, Sha3_512};
 
     fn key_pair<T, PQ>() -> (DecapsulationKey<T, PQ>, EncapsulationKey<T, PQ>)
     where
@@ -37,7 +48,7 @@ mod tests {
         PQ: BaseKem,
     {
         let (dk, ek) = key_pair::<T, PQ>();
-        test_encap_decap(&KitchenSink, dk, ek);
+        test_encap_decap(&KitchenSink::new(), dk, ek);
     }
 
     #[test]
@@ -47,7 +58,7 @@ mod tests {
         PQ: BaseKem,
     {
         let (dk, ek) = key_pair::<T, PQ>();
-        let kitchen_sink_pre = KitchenSinkPre::new_hybrid(&ek);
+        let kitchen_sink_pre: KitchenSinkPre = KitchenSinkPre::new_hybrid(&ek);
         test_encap_decap(&kitchen_sink_pre, dk, ek);
     }
 
@@ -58,8 +69,8 @@ mod tests {
         PQ: BaseKem,
     {
         let (dk, ek) = key_pair::<T, PQ>();
-        let kitchen_sink = KitchenSink;
-        let kitchen_sink_pre = KitchenSinkPre::new_hybrid(&ek);
+        let kitchen_sink = KitchenSink::new();
+        let kitchen_sink_pre: KitchenSinkPre = KitchenSinkPre::new_hybrid(&ek);
 
         let mut rng = rand::thread_rng();
         let (ct, ss_e) = HybridKem::encap(&kitchen_sink, &mut rng, &ek);
@@ -79,7 +90,7 @@ mod tests {
         PQ: BaseKem,
     {
         let (dk, ek) = key_pair::<T, PQ>();
-        test_encap_decap(&Chempat, dk, ek);
+        test_encap_decap(&Chempat::new(), dk, ek);
     }
 
     #[test]
@@ -89,7 +100,7 @@ mod tests {
         PQ: BaseKem,
     {
         let (dk, ek) = key_pair::<T, PQ>();
-        let chempat_pre = ChempatPre::new_hybrid(&ek);
+        let chempat_pre: ChempatPre = ChempatPre::new_hybrid(&ek);
         test_encap_decap(&chempat_pre, dk, ek);
     }
 
@@ -100,8 +111,8 @@ mod tests {
         PQ: BaseKem,
     {
         let (dk, ek) = key_pair::<T, PQ>();
-        let chempat = Chempat;
-        let chempat_pre = ChempatPre::new_hybrid(&ek);
+        let chempat = Chempat::new();
+        let chempat_pre: ChempatPre = ChempatPre::new_hybrid(&ek);
 
         let mut rng = rand::thread_rng();
         let (ct, ss_e) = HybridKem::encap(&chempat, &mut rng, &ek);
@@ -114,6 +125,67 @@ mod tests {
         assert_eq!(ss_e, ss_d);
     }
 
+    #[test]
+    fn dual_prf<T, PQ>()
+    where
+        T: BaseKem,
+        PQ: BaseKem,
+    {
+        let (dk, ek) = key_pair::<T, PQ>();
+        test_encap_decap(&DualPrf::new(), dk, ek);
+    }
+
+    #[test]
+    fn dual_prf_pre<T, PQ>()
+    where
+        T: BaseKem,
+        PQ: BaseKem,
+    {
+        let (dk, ek) = key_pair::<T, PQ>();
+        let dual_prf_pre: DualPrfPre = DualPrfPre::new_hybrid(&ek);
+        test_encap_decap(&dual_prf_pre, dk, ek);
+    }
+
+    #[test]
+    fn dual_prf_pre_eq<T, PQ>()
+    where
+        T: BaseKem,
+        PQ: BaseKem,
+    {
+        let (dk, ek) = key_pair::<T, PQ>();
+        let dual_prf = DualPrf::new();
+        let dual_prf_pre: DualPrfPre = DualPrfPre::new_hybrid(&ek);
+
+        let mut rng = rand::thread_rng();
+        let (ct, ss_e) = HybridKem::encap(&dual_prf, &mut rng, &ek);
+        let ss_d = HybridKem::decap(&dual_prf_pre, &dk, &ct);
+        assert_eq!(ss_e, ss_d);
+
+        let mut rng = rand::thread_rng();
+        let (ct, ss_e) = HybridKem::encap(&dual_prf_pre, &mut rng, &ek);
+        let ss_d = HybridKem::decap(&dual_prf, &dk, &ct);
+        assert_eq!(ss_e, ss_d);
+    }
+
+    #[test]
+    fn chempat_labeled<T, PQ>()
+    where
+        T: BaseKem,
+        PQ: BaseKem,
+    {
+        let (dk, ek) = key_pair::<T, PQ>();
+        test_encap_decap(&CHEMPAT_X25519_SNTRUP761, dk, ek);
+
+        let (dk, ek) = key_pair::<T, PQ>();
+        test_encap_decap(&CHEMPAT_X25519_MLKEM768, dk, ek);
+
+        let (dk, ek) = key_pair::<T, PQ>();
+        test_encap_decap(&CHEMPAT_X448_MLKEM1024, dk, ek);
+
+        let (dk, ek) = key_pair::<T, PQ>();
+        test_encap_decap(&CHEMPAT_X448_SNTRUP761, dk, ek);
+    }
+
     #[test]
     fn dhkem<T, PQ>()
     where
@@ -121,7 +193,7 @@ mod tests {
         PQ: BaseKem,
     {
         let (dk, ek) = key_pair::<T, PQ>();
-        test_encap_decap(&Dhkem, dk, ek);
+        test_encap_decap(&Dhkem::new(), dk, ek);
     }
 
     #[test]
@@ -131,7 +203,7 @@ mod tests {
         PQ: BaseKem,
     {
         let (dk, ek) = key_pair::<T, PQ>();
-        let dhkem_pre = DhkemPre::new_hybrid(&ek);
+        let dhkem_pre: DhkemPre = DhkemPre::new_hybrid(&ek);
         test_encap_decap(&dhkem_pre, dk, ek);
     }
 
@@ -142,8 +214,8 @@ mod tests {
         PQ: BaseKem,
     {
         let (dk, ek) = key_pair::<T, PQ>();
-        let dhkem = Dhkem;
-        let dhkem_pre = DhkemPre::new_hybrid(&ek);
+        let dhkem = Dhkem::new();
+        let dhkem_pre: DhkemPre = DhkemPre::new_hybrid(&ek);
 
         let mut rng = rand::thread_rng();
         let (ct, ss_e) = HybridKem::encap(&dhkem, &mut rng, &ek);
@@ -163,7 +235,7 @@ mod tests {
         PQ: BaseKem,
     {
         let (dk, ek) = key_pair::<T, PQ>();
-        test_encap_decap(&DhkemHalf, dk, ek);
+        test_encap_decap(&DhkemHalf::new(), dk, ek);
     }
 
     #[test]
@@ -173,10 +245,361 @@ mod tests {
         PQ: BaseKem,
     {
         let (dk, ek) = key_pair::<T, PQ>();
-        test_encap_decap(&XWing, dk, ek);
+        test_encap_decap(&XWing::new(), dk, ek);
+    }
+
+    #[test]
+    fn xwing_labeled<T, PQ>()
+    where
+        T: BaseKem,
+        PQ: BaseKem,
+    {
+        let (dk, ek) = key_pair::<T, PQ>();
+        test_encap_decap(&XWingLabeled::new(), dk, ek);
+    }
+
+    #[test]
+    fn ghp<T, PQ>()
+    where
+        T: BaseKem,
+        PQ: BaseKem,
+    {
+        let (dk, ek) = key_pair::<T, PQ>();
+        let ghp: Ghp = Ghp::new(b"GHP");
+        test_encap_decap(&ghp, dk, ek);
+    }
+
+    #[test]
+    fn pre<T, PQ>()
+    where
+        T: BaseKem,
+        PQ: BaseKem,
+    {
+        let (dk, ek) = key_pair::<T, PQ>();
+        let pre: Pre = Pre::new(b"PRE");
+        test_encap_decap(&pre, dk, ek);
+    }
+
+    #[test]
+    fn pre_pre<T, PQ>()
+    where
+        T: BaseKem,
+        PQ: BaseKem,
+    {
+        let (dk, ek) = key_pair::<T, PQ>();
+        let pre_pre: PrePre = PrePre::new(b"PRE", ek.t.as_ref(), ek.pq.as_ref());
+        test_encap_decap(&pre_pre, dk, ek);
+    }
+
+    #[test]
+    fn pre_pre_eq<T, PQ>()
+    where
+        T: BaseKem,
+        PQ: BaseKem,
+    {
+        let (dk, ek) = key_pair::<T, PQ>();
+        let pre: Pre = Pre::new(b"PRE");
+        let pre_pre: PrePre = PrePre::new(b"PRE", ek.t.as_ref(), ek.pq.as_ref());
+
+        let mut rng = rand::thread_rng();
+        let (ct, ss_e) = HybridKem::encap(&pre, &mut rng, &ek);
+        let ss_d = HybridKem::decap(&pre_pre, &dk, &ct);
+        assert_eq!(ss_e, ss_d);
+
+        let mut rng = rand::thread_rng();
+        let (ct, ss_e) = HybridKem::encap(&pre_pre, &mut rng, &ek);
+        let ss_d = HybridKem::decap(&pre, &dk, &ct);
+        assert_eq!(ss_e, ss_d);
+    }
+
+    #[test]
+    fn qsf<T, PQ>()
+    where
+        T: BaseKem,
+        PQ: BaseKem,
+    {
+        let (dk, ek) = key_pair::<T, PQ>();
+        let qsf: Qsf = Qsf::new(b"QSF");
+        test_encap_decap(&qsf, dk, ek);
+    }
+
+    #[test]
+    fn composite<T, PQ>()
+    where
+        T: BaseKem,
+        PQ: BaseKem,
+    {
+        let (dk, ek) = key_pair::<T, PQ>();
+        test_encap_decap(&Composite::from(ID_MLKEM768_X25519), dk, ek);
+
+        let (dk, ek) = key_pair::<T, PQ>();
+        test_encap_decap(&Composite::from(ID_MLKEM768_RSA2048), dk, ek);
+    }
+
+    #[test]
+    fn tls13<T, PQ>()
+    where
+        T: BaseKem,
+        PQ: BaseKem,
+    {
+        let (dk, ek) = key_pair::<T, PQ>();
+        test_encap_decap(&Tls13Sha256::new(), dk, ek);
+
+        let (dk, ek) = key_pair::<T, PQ>();
+        test_encap_decap(&Tls13Sha384::new(), dk, ek);
+    }
+
+    #[test]
+    fn ikev2<T, PQ>()
+    where
+        T: BaseKem,
+        PQ: BaseKem,
+    {
+        let mut rng = rand::thread_rng();
+        let (mut ni, mut nr) = ([0u8; 32], [0u8; 32]);
+        let (mut spi_i, mut spi_r) = ([0u8; 8], [0u8; 8]);
+        rng.fill_bytes(&mut ni);
+        rng.fill_bytes(&mut nr);
+        rng.fill_bytes(&mut spi_i);
+        rng.fill_bytes(&mut spi_r);
+
+        let (dk, ek) = key_pair::<T, PQ>();
+        test_encap_decap(&Ikev2Sha256::new(&ni, &nr, spi_i, spi_r), dk, ek);
+
+        let (dk, ek) = key_pair::<T, PQ>();
+        test_encap_decap(&Ikev2Sha384::new(&ni, &nr, spi_i, spi_r), dk, ek);
+
+        let (dk, ek) = key_pair::<T, PQ>();
+        test_encap_decap(&Ikev2Sha512::new(&ni, &nr, spi_i, spi_r), dk, ek);
+    }
+
+    #[test]
+    fn openpgp<T, PQ>()
+    where
+        T: BaseKem,
+        PQ: BaseKem,
+    {
+        let alg_id = OPENPGP_MLKEM768_X25519;
+
+        let (dk, ek) = key_pair::<T, PQ>();
+        test_encap_decap(&OpenPgpKmac { alg_id }, dk, ek);
+
+        let (dk, ek) = key_pair::<T, PQ>();
+        test_encap_decap(&OpenPgpSha3 { alg_id }, dk, ek);
+    }
+
+    #[test]
+    fn etsi<T, PQ>()
+    where
+        T: BaseKem,
+        PQ: BaseKem,
+    {
+        let (dk, ek) = key_pair::<T, PQ>();
+        test_encap_decap(&CatKdf::<HkdfSha256Kdf>::new(&[], b"ETSI", &[]), dk, ek);
+
+        let (dk, ek) = key_pair::<T, PQ>();
+        test_encap_decap(&CasKdf::<HkdfSha256Kdf>::new(&[], b"ETSI", &[]), dk, ek);
+
+        let (dk, ek) = key_pair::<T, PQ>();
+        test_encap_decap(&CatKdf::<Kmac256Kdf>::new(&[], b"ETSI", &[]), dk, ek);
+
+        let (dk, ek) = key_pair::<T, PQ>();
+        test_encap_decap(&CasKdf::<Kmac256Kdf>::new(&[], b"ETSI", &[]), dk, ek);
+    }
+
+    #[test]
+    fn pqxdh<T, PQ>()
+    where
+        T: BaseKem,
+        PQ: BaseKem,
+    {
+        let (dk, ek) = key_pair::<T, PQ>();
+        let pqxdh = Pqxdh {
+            info: PQXDH_SIGNAL_INFO.to_vec(),
+        };
+        test_encap_decap(&pqxdh, dk, ek);
+    }
+
+    #[test]
+    fn ssh<T, PQ>()
+    where
+        T: BaseKem,
+        PQ: BaseKem,
+    {
+        let (dk, ek) = key_pair::<T, PQ>();
+        test_encap_decap(&SshSha256::new(), dk, ek);
+
+        let (dk, ek) = key_pair::<T, PQ>();
+        test_encap_decap(&SshSha512::new(), dk, ek);
+    }
+
+    // Encapsulates with the plain combiner and decapsulates with the pre-hashed one, so that both
+    // are exercised with the given hash
+    fn test_hash<T, PQ, H, C, P>(dk: &DecapsulationKey<T, PQ>, ek: &EncapsulationKey<T, PQ>)
+    where
+        T: BaseKem,
+        PQ: BaseKem,
+        H: Digest,
+        C: Combiner<Hash = H> + Default,
+        P: Combiner<Hash = H> + NewPre,
+    {
+        let c = C::default();
+        let pre = P::new_hybrid(ek);
+
+        let mut rng = rand::thread_rng();
+        let (ct, ss_e) = HybridKem::encap(&c, &mut rng, ek);
+        let ss_d = HybridKem::decap(&pre, dk, &ct);
+        assert_eq!(ss_e, ss_d);
+        assert_eq!(ss_e.len(), <H as Digest>::output_size());
+    }
+
+    fn test_hashes<T, PQ, H>(dk: &DecapsulationKey<T, PQ>, ek: &EncapsulationKey<T, PQ>)
+    where
+        T: BaseKem,
+        PQ: BaseKem,
+        H: Digest + BlockSizeUser + Clone,
+    {
+        test_hash::<T, PQ, H, KitchenSink<H>, KitchenSinkPre<H>>(dk, ek);
+        test_hash::<T, PQ, H, Chempat<H>, ChempatPre<H>>(dk, ek);
+        test_hash::<T, PQ, H, Dhkem<H>, DhkemPre<H>>(dk, ek);
+        test_hash::<T, PQ, H, DualPrf<H>, DualPrfPre<H>>(dk, ek);
+    }
+
+    #[test]
+    fn hashes<T, PQ>()
+    where
+        T: BaseKem,
+        PQ: BaseKem,
+    {
+        let (dk, ek) = key_pair::<T, PQ>();
+        test_hashes::<T, PQ, Sha256>(&dk, &ek);
+        test_hashes::<T, PQ, Sha384>(&dk, &ek);
+        test_hashes::<T, PQ, Sha512>(&dk, &ek);
+        test_hashes::<T, PQ, Sha3_256>(&dk, &ek);
+        test_hashes::<T, PQ, Sha3_512>(&dk, &ek);
+        test_hashes::<T, PQ, Blake2b512>(&dk, &ek);
+        test_hashes::<T, PQ, blake3::Hasher>(&dk, &ek);
+    }
+
+    #[instantiate_tests(<X25519, MlKem512>)]
+    mod x25519_ml_kem_512 {}
+
+    #[instantiate_tests(<X25519, MlKem768>)]
+    mod x25519_ml_kem {}
+
+    #[instantiate_tests(<X25519, MlKem1024>)]
+    mod x25519_ml_kem_1024 {}
+
+    #[instantiate_tests(<RsaKem2048, MlKem768>)]
+    mod rsa_kem_2048_ml_kem {}
+
+    #[instantiate_tests(<X25519, Kyber768Round3>)]
+    mod x25519_kyber768_round3 {}
+
+    #[instantiate_tests(<X448, MlKem1024>)]
+    mod x448_ml_kem_1024 {}
+
+    #[instantiate_tests(<DhkemX25519HkdfSha256, MlKem768>)]
+    mod dhkem_x25519_ml_kem {}
+
+    #[instantiate_tests(<DhkemP256HkdfSha256, MlKem768>)]
+    mod dhkem_p256_ml_kem {}
+
+    #[instantiate_tests(<P256, MlKem768>)]
+    mod p256_ml_kem {}
+
+    #[instantiate_tests(<P384, MlKem1024>)]
+    mod p384_ml_kem_1024 {}
+
+    #[instantiate_tests(<P521, MlKem1024>)]
+    mod p521_ml_kem_1024 {}
+
+    #[instantiate_tests(<X25519, FrodoKem640Aes>)]
+    mod x25519_frodo_kem_640_aes {}
+
+    #[instantiate_tests(<X25519, FrodoKem640Shake>)]
+    mod x25519_frodo_kem_640_shake {}
+
+    #[instantiate_tests(<P384, FrodoKem976Shake>)]
+    mod p384_frodo_kem_976_shake {}
+
+    #[instantiate_tests(<X448, FrodoKem1344Shake>)]
+    mod x448_frodo_kem_1344_shake {}
+
+    #[instantiate_tests(<X25519, Hqc128>)]
+    mod x25519_hqc_128 {}
+
+    #[instantiate_tests(<P384, Hqc192>)]
+    mod p384_hqc_192 {}
+
+    #[instantiate_tests(<X448, Hqc256>)]
+    mod x448_hqc_256 {}
+
+    #[instantiate_tests(<X25519, Sntrup761>)]
+    mod x25519_sntrup761 {}
+
+    #[instantiate_tests(<X25519, ClassicMcEliece>)]
+    mod x25519_classic_mceliece {}
+
+    #[instantiate_tests(<MockKem<MockEmpty>, MockKem<MockEmpty>>)]
+    mod mock_empty {}
+
+    #[instantiate_tests(<X25519, MockKem<MockLarge>>)]
+    mod x25519_mock_large {}
+}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod deterministic_tests {
+    use crate::base::*;
+    use crate::combiners::*;
+    use crate::hybrid::*;
+    use rand::RngCore;
+
+    #[test]
+    fn derive_key_pair<T, PQ>()
+    where
+        T: SeedKem,
+        PQ: SeedKem,
+    {
+        let (dk, ek_1) = HybridKem::<T, PQ>::derive_key_pair(b"input keying material");
+        let (_dk, ek_2) = HybridKem::<T, PQ>::derive_key_pair(b"input keying material");
+        assert_eq!(ek_1.t.as_ref(), ek_2.t.as_ref());
+        assert_eq!(ek_1.pq.as_ref(), ek_2.pq.as_ref());
+
+        let (_dk, ek_3) = HybridKem::<T, PQ>::derive_key_pair(b"other keying material");
+        assert_ne!(ek_1.t.as_ref(), ek_3.t.as_ref());
+        assert_ne!(ek_1.pq.as_ref(), ek_3.pq.as_ref());
+
+        let mut rng = rand::thread_rng();
+        let (ct, ss_e) = HybridKem::encap(&KitchenSink::new(), &mut rng, &ek_1);
+        let ss_d = HybridKem::decap(&KitchenSink::new(), &dk, &ct);
+        assert_eq!(ss_e, ss_d);
+    }
+
+    #[test]
+    fn encap_derand<T, PQ>()
+    where
+        T: DerandKem,
+        PQ: DerandKem,
+    {
+        let mut rng = rand::thread_rng();
+        let (dk, ek) = HybridKem::<T, PQ>::generate(&mut rng);
+
+        let mut randomness = vec![0u8; T::ENCAP_RANDOMNESS_LEN + PQ::ENCAP_RANDOMNESS_LEN];
+        rng.fill_bytes(&mut randomness);
+
+        let (ct_1, ss_1) = HybridKem::encap_derand(&KitchenSink::new(), &randomness, &ek);
+        let (ct_2, ss_2) = HybridKem::encap_derand(&KitchenSink::new(), &randomness, &ek);
+        assert_eq!(ct_1.t.as_ref(), ct_2.t.as_ref());
+        assert_eq!(ct_1.pq.as_ref(), ct_2.pq.as_ref());
+        assert_eq!(ss_1, ss_2);
+
+        let ss_d = HybridKem::decap(&KitchenSink::new(), &dk, &ct_1);
+        assert_eq!(ss_1, ss_d);
     }
 
-    #[instantiate_tests(<X25519, MlKem>)]
+    #[instantiate_tests(<X25519, MlKem768>)]
     mod x25519_ml_kem {}
 
     #[instantiate_tests(<X25519, ClassicMcEliece>)]
@@ -0,0 +1,139 @@
+use std::marker::PhantomData;
+
+use rand_core::CryptoRngCore;
+use sha3::{
+    digest::{ExtendableOutput, Update},
+    Shake256,
+};
+
+use crate::base::BaseKem;
+
+// Determines the behavior of a MockKem.  By default, keys and ciphertexts are random byte strings
+// of the indicated lengths, and the shared secret is a hash of the encapsulation key and
+// ciphertext, so that encap and decap agree.  Any of these can be overridden to produce
+// adversarial outputs.
+pub trait MockParams {
+    const EK_LEN: usize;
+    const CT_LEN: usize;
+    const SS_LEN: usize;
+
+    fn encapsulation_key(rng: &mut impl CryptoRngCore) -> Vec<u8> {
+        let mut ek = vec![0u8; Self::EK_LEN];
+        rng.fill_bytes(&mut ek);
+        ek
+    }
+
+    fn ciphertext(rng: &mut impl CryptoRngCore, _ek: &[u8]) -> Vec<u8> {
+        let mut ct = vec![0u8; Self::CT_LEN];
+        rng.fill_bytes(&mut ct);
+        ct
+    }
+
+    fn shared_secret(ek: &[u8], ct: &[u8]) -> Vec<u8> {
+        let mut h = Shake256::default();
+        h.update(ek);
+        h.update(ct);
+
+        let mut ss = vec![0u8; Self::SS_LEN];
+        h.finalize_xof_into(&mut ss);
+        ss
+    }
+}
+
+// A KEM whose behavior is set by the test, for testing combiners in isolation.  The decapsulation
+// key is just a copy of the encapsulation key.
+pub struct MockKem<P>(PhantomData<P>);
+
+impl<P: MockParams> BaseKem for MockKem<P> {
+    type DecapsulationKey = Vec<u8>;
+    type EncapsulationKey = Vec<u8>;
+    type Ciphertext = Vec<u8>;
+    type SharedSecret = Vec<u8>;
+
+    fn generate(rng: &mut impl CryptoRngCore) -> (Self::DecapsulationKey, Self::EncapsulationKey) {
+        let ek = P::encapsulation_key(rng);
+        (ek.clone(), ek)
+    }
+
+    fn encap(
+        rng: &mut impl CryptoRngCore,
+        ek: &Self::EncapsulationKey,
+    ) -> (Self::Ciphertext, Self::SharedSecret) {
+        let ct = P::ciphertext(rng, ek);
+        let ss = P::shared_secret(ek, &ct);
+        (ct, ss)
+    }
+
+    fn decap(dk: &Self::DecapsulationKey, ct: &Self::Ciphertext) -> Self::SharedSecret {
+        P::shared_secret(dk, ct)
+    }
+}
+
+// Empty keys, ciphertexts, and shared secrets
+pub struct MockEmpty;
+
+impl MockParams for MockEmpty {
+    const EK_LEN: usize = 0;
+    const CT_LEN: usize = 0;
+    const SS_LEN: usize = 0;
+}
+
+// Multi-megabyte keys and ciphertexts
+pub struct MockLarge;
+
+impl MockParams for MockLarge {
+    const EK_LEN: usize = 2 << 20;
+    const CT_LEN: usize = 2 << 20;
+    const SS_LEN: usize = 32;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::X25519;
+    use crate::combiners::*;
+    use crate::hybrid::*;
+
+    // The same shared secret for every ciphertext, as with a KEM that is not ciphertext-binding
+    struct ConstantSharedSecret;
+
+    impl MockParams for ConstantSharedSecret {
+        const EK_LEN: usize = 32;
+        const CT_LEN: usize = 32;
+        const SS_LEN: usize = 32;
+
+        fn shared_secret(_ek: &[u8], _ct: &[u8]) -> Vec<u8> {
+            vec![0x42; Self::SS_LEN]
+        }
+    }
+
+    // Encapsulate twice to the same key with the same traditional component, so that the two
+    // hybrid ciphertexts differ only in the PQ ciphertext
+    fn pq_ciphertext_changed<C: Combiner>(c: &C) -> bool {
+        type Kem = HybridKem<X25519, MockKem<ConstantSharedSecret>>;
+
+        let mut rng = rand::thread_rng();
+        let (dk, ek) = Kem::generate(&mut rng);
+        let (ct_1, ss_1) = Kem::encap(c, &mut rng, &ek);
+
+        let ct_2 = Ciphertext {
+            t: ct_1.t,
+            pq: MockKem::<ConstantSharedSecret>::encap(&mut rng, &ek.pq).0,
+        };
+        assert_ne!(ct_1.pq, ct_2.pq);
+
+        let ss_2 = Kem::decap(c, &dk, &ct_2);
+        ss_1 != ss_2
+    }
+
+    #[test]
+    fn ciphertext_binding() {
+        assert!(pq_ciphertext_changed(&KitchenSink::new()));
+        assert!(pq_ciphertext_changed(&Chempat::new()));
+        assert!(pq_ciphertext_changed(&Dhkem::new()));
+
+        // These combiners rely on the PQ KEM to bind its ciphertext
+        assert!(!pq_ciphertext_changed(&DhkemHalf::new()));
+        assert!(!pq_ciphertext_changed(&XWing::new()));
+    }
+}
@@ -0,0 +1,285 @@
+use hybrid_array::{
+    sizes::{U1120, U1216},
+    Array,
+};
+use rand_core::CryptoRngCore;
+use sha3::{
+    digest::{ExtendableOutput, Update, XofReader},
+    Shake256,
+};
+
+use crate::base::{BaseKem, DecodeError, DecodeKem, DerandKem, MlKem768, SeedKem, X25519};
+use crate::combiners::{Combiner, XWingLabeled};
+use crate::hybrid::SharedSecret;
+
+// X-Wing as specified in draft-connolly-cfrg-xwing-kem-06.  Unlike HybridKem<X25519, MlKem768>
+// with the XWingLabeled combiner, which produces the same shared secrets, this uses the spec's
+// key and ciphertext encodings:
+//
+// * Decapsulation keys are a 32-byte seed, expanded with SHAKE256 into the component keys
+// * Encapsulation keys are ek_M || ek_X
+// * Ciphertexts are ct_M || ct_X
+pub struct XWingKem;
+
+const ML_KEM_CT_LEN: usize = 1088;
+
+pub struct XWingDecapsulationKey {
+    seed: [u8; 32],
+    dk_m: <MlKem768 as BaseKem>::DecapsulationKey,
+    dk_x: <X25519 as BaseKem>::DecapsulationKey,
+    ek: XWingEncapsulationKey,
+}
+
+impl XWingDecapsulationKey {
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.seed
+    }
+}
+
+#[derive(Clone)]
+pub struct XWingEncapsulationKey {
+    ek_m: <MlKem768 as BaseKem>::EncapsulationKey,
+    ek_x: <X25519 as BaseKem>::EncapsulationKey,
+    ek_bytes: Array<u8, U1216>,
+}
+
+impl AsRef<[u8]> for XWingEncapsulationKey {
+    fn as_ref(&self) -> &[u8] {
+        self.ek_bytes.as_slice()
+    }
+}
+
+// Parses ek_M || ek_X, applying the ML-KEM modulus check to ek_M
+impl TryFrom<&[u8]> for XWingEncapsulationKey {
+    type Error = DecodeError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let ek_bytes = Array::<u8, U1216>::try_from(bytes).map_err(|_| DecodeError)?;
+        let (ek_m, ek_x) = bytes.split_at(MlKem768::EK_LEN);
+        Ok(Self {
+            ek_m: MlKem768::decode_ek(ek_m)?,
+            ek_x: X25519::decode_ek(ek_x)?,
+            ek_bytes,
+        })
+    }
+}
+
+impl XWingKem {
+    fn expand_decapsulation_key(seed: [u8; 32]) -> XWingDecapsulationKey {
+        let mut h = Shake256::default();
+        h.update(&seed);
+        let mut reader = h.finalize_xof();
+
+        let mut seed_m = [0u8; 64];
+        let mut seed_x = [0u8; 32];
+        reader.read(&mut seed_m);
+        reader.read(&mut seed_x);
+
+        let (dk_m, ek_m) = MlKem768::generate_from_seed(&seed_m);
+        let (dk_x, ek_x) = X25519::generate_from_seed(&seed_x);
+
+        let mut ek_bytes = Array::<u8, U1216>::default();
+        let (ek_bytes_m, ek_bytes_x) = ek_bytes.split_at_mut(ek_m.as_ref().len());
+        ek_bytes_m.copy_from_slice(ek_m.as_ref());
+        ek_bytes_x.copy_from_slice(ek_x.as_ref());
+
+        let ek = XWingEncapsulationKey {
+            ek_m,
+            ek_x,
+            ek_bytes,
+        };
+        XWingDecapsulationKey {
+            seed,
+            dk_m,
+            dk_x,
+            ek,
+        }
+    }
+
+    fn ciphertext(ct_m: &[u8], ct_x: &[u8]) -> Array<u8, U1120> {
+        let mut ct = Array::<u8, U1120>::default();
+        let (ct_bytes_m, ct_bytes_x) = ct.split_at_mut(ML_KEM_CT_LEN);
+        ct_bytes_m.copy_from_slice(ct_m);
+        ct_bytes_x.copy_from_slice(ct_x);
+        ct
+    }
+}
+
+impl BaseKem for XWingKem {
+    type DecapsulationKey = XWingDecapsulationKey;
+    type EncapsulationKey = XWingEncapsulationKey;
+    type Ciphertext = Array<u8, U1120>;
+    type SharedSecret = SharedSecret;
+
+    fn generate(rng: &mut impl CryptoRngCore) -> (Self::DecapsulationKey, Self::EncapsulationKey) {
+        let mut seed = [0u8; 32];
+        rng.fill_bytes(&mut seed);
+        Self::generate_from_seed(&seed)
+    }
+
+    fn encap(
+        rng: &mut impl CryptoRngCore,
+        ek: &Self::EncapsulationKey,
+    ) -> (Self::Ciphertext, Self::SharedSecret) {
+        let mut randomness = [0u8; 64];
+        rng.fill_bytes(&mut randomness);
+        Self::encap_derand(ek, &randomness)
+    }
+
+    fn decap(dk: &Self::DecapsulationKey, ct: &Self::Ciphertext) -> Self::SharedSecret {
+        let (ct_m, ct_x) = ct.split_at(ML_KEM_CT_LEN);
+        let ct_m = ct_m.try_into().unwrap();
+        let ct_x: [u8; 32] = ct_x.try_into().unwrap();
+        let ct_x = x25519_dalek::PublicKey::from(ct_x);
+
+        let ss_m = MlKem768::decap(&dk.dk_m, &ct_m);
+        let ss_x = X25519::decap(&dk.dk_x, &ct_x);
+        XWingLabeled::new().combine(
+            ss_x.as_bytes(),
+            ct_x.as_bytes(),
+            dk.ek.ek_x.as_bytes(),
+            &ss_m,
+            &[],
+            &[],
+        )
+    }
+}
+
+impl DecodeKem for XWingKem {
+    const EK_LEN: usize = 1216;
+    const CT_LEN: usize = 1120;
+
+    fn decode_ek(bytes: &[u8]) -> Result<Self::EncapsulationKey, DecodeError> {
+        XWingEncapsulationKey::try_from(bytes)
+    }
+
+    fn decode_ct(bytes: &[u8]) -> Result<Self::Ciphertext, DecodeError> {
+        Array::try_from(bytes).map_err(|_| DecodeError)
+    }
+}
+
+impl SeedKem for XWingKem {
+    const ID: &'static [u8] = b"X-Wing";
+    const SEED_LEN: usize = 32;
+
+    fn generate_from_seed(seed: &[u8]) -> (Self::DecapsulationKey, Self::EncapsulationKey) {
+        assert_eq!(seed.len(), Self::SEED_LEN);
+        let dk = Self::expand_decapsulation_key(seed.try_into().unwrap());
+        let ek = dk.ek.clone();
+        (dk, ek)
+    }
+}
+
+// The first 32 bytes of randomness go to ML-KEM and the last 32 to X25519
+impl DerandKem for XWingKem {
+    const ENCAP_RANDOMNESS_LEN: usize = 64;
+
+    fn encap_derand(
+        ek: &Self::EncapsulationKey,
+        randomness: &[u8],
+    ) -> (Self::Ciphertext, Self::SharedSecret) {
+        assert_eq!(randomness.len(), Self::ENCAP_RANDOMNESS_LEN);
+        let (randomness_m, randomness_x) = randomness.split_at(32);
+
+        let (ct_m, ss_m) = MlKem768::encap_derand(&ek.ek_m, randomness_m);
+        let (ct_x, ss_x) = X25519::encap_derand(&ek.ek_x, randomness_x);
+
+        let ss = XWingLabeled::new().combine(
+            ss_x.as_bytes(),
+            ct_x.as_bytes(),
+            ek.ek_x.as_bytes(),
+            &ss_m,
+            &[],
+            &[],
+        );
+        (Self::ciphertext(&ct_m, ct_x.as_bytes()), ss)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::hybrid::HybridKem;
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct TestVector {
+        #[serde(with = "hex::serde")]
+        seed: Vec<u8>,
+        #[serde(with = "hex::serde")]
+        eseed: Vec<u8>,
+        #[serde(with = "hex::serde")]
+        ss: Vec<u8>,
+        #[serde(with = "hex::serde")]
+        sk: Vec<u8>,
+        #[serde(with = "hex::serde")]
+        pk: Vec<u8>,
+        #[serde(with = "hex::serde")]
+        ct: Vec<u8>,
+    }
+
+    #[test]
+    fn encap_decap() {
+        let mut rng = rand::thread_rng();
+        let (dk, ek) = XWingKem::generate(&mut rng);
+        let (ct, ss_e) = XWingKem::encap(&mut rng, &ek);
+        let ss_d = XWingKem::decap(&dk, &ct);
+        assert_eq!(ss_e, ss_d);
+    }
+
+    // Test vectors from the draft repository (spec/test-vectors.json)
+    #[test]
+    fn test_vectors() {
+        let tvs: Vec<TestVector> =
+            serde_json::from_str(include_str!("../test-vectors/xwing.json")).unwrap();
+
+        for tv in tvs {
+            let (dk, ek) = XWingKem::generate_from_seed(&tv.seed);
+            assert_eq!(dk.as_bytes().as_slice(), &tv.sk);
+            assert_eq!(ek.as_ref(), &tv.pk);
+
+            // Encapsulate to the published key and decapsulate the published ciphertext
+            let ek = XWingKem::decode_ek(&tv.pk).unwrap();
+            let (ct, ss) = XWingKem::encap_derand(&ek, &tv.eseed);
+            assert_eq!(ct.as_slice(), &tv.ct);
+            assert_eq!(ss.as_slice(), &tv.ss);
+
+            let ct = XWingKem::decode_ct(&tv.ct).unwrap();
+            let ss = XWingKem::decap(&dk, &ct);
+            assert_eq!(ss.as_slice(), &tv.ss);
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_keys() {
+        let (_, ek) = XWingKem::generate(&mut rand::thread_rng());
+        assert!(XWingKem::decode_ek(&ek.as_ref()[1..]).is_err());
+
+        // A coefficient equal to q fails the ML-KEM modulus check
+        let mut bytes = ek.as_ref().to_vec();
+        bytes[0] = 0x01;
+        bytes[1] = (bytes[1] & 0xf0) | 0x0d;
+        assert!(XWingKem::decode_ek(&bytes).is_err());
+    }
+
+    // X-Wing is HybridKem<X25519, MlKem768> with the XWingLabeled combiner, up to encodings
+    #[test]
+    fn matches_hybrid_kem() {
+        let mut rng = rand::thread_rng();
+        let (dk, ek) = HybridKem::<X25519, MlKem768>::generate(&mut rng);
+        let (ct, ss) = HybridKem::encap(&XWingLabeled::new(), &mut rng, &ek);
+
+        let dk = XWingDecapsulationKey {
+            seed: [0; 32],
+            dk_m: dk.pq,
+            dk_x: dk.t,
+            ek: XWingEncapsulationKey {
+                ek_m: ek.pq.clone(),
+                ek_x: ek.t,
+                ek_bytes: Default::default(),
+            },
+        };
+        let ct = XWingKem::ciphertext(&ct.pq, ct.t.as_bytes());
+        assert_eq!(XWingKem::decap(&dk, &ct), ss);
+    }
+}